@@ -1,18 +1,19 @@
+use crate::util::units::HeapUnit;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
 #[command(name = env!("CARGO_PKG_NAME"), version = env!("CARGO_PKG_VERSION"))]
 pub struct Args {
-    /// GC log file path
-    #[arg(short, long)]
-    pub input: String,
+    /// GC log files, directories or glob patterns (.gz / .zst are decompressed, - for stdin)
+    #[arg(short, long, num_args = 1.., required = true)]
+    pub input: Vec<String>,
 
     /// Output PNG file
     #[arg(short, long, default_value = "output.png")]
     pub plot: String,
 
-    /// Rendering mode: heap, pause, combined
-    #[arg(short, long, default_value = "combined")]
+    /// Rendering mode: heap, pause, combined, metaspace
+    #[arg(short, long, default_value = "combined", value_parser = ["heap", "pause", "combined", "metaspace"])]
     pub mode: String,
 
     /// CSV output destination (optional)
@@ -21,4 +22,16 @@ pub struct Args {
 
     #[arg(long, help = "Display number of GC types")]
     pub summary: bool,
+
+    /// Heap units for CSV, summary and charts: bytes, MB, GB, regions (default: regions for G1 region counts, bytes otherwise)
+    #[arg(short, long, value_parser = HeapUnit::parse_arg)]
+    pub units: Option<String>,
+
+    /// Analysis report: imbalance, cpu, consistency, humongous, tenuring, references
+    #[arg(short, long, value_parser = ["imbalance", "cpu", "consistency", "humongous", "tenuring", "references"])]
+    pub analyze: Option<String>,
+
+    /// Keep the log open like `tail -F` and print a summary line per GC
+    #[arg(short, long)]
+    pub follow: bool,
 }
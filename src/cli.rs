@@ -1,3 +1,4 @@
+use crate::util::units::HeapUnit;
 use clap::{Arg, ArgAction, Command};
 
 const APP_NAME: &str = env!("CARGO_PKG_NAME");
@@ -12,9 +13,10 @@ pub fn generate_cli_app() -> Command {
                 .long("input")
                 .short('i')
                 .value_name("FILE")
-                .help("Specify the GC log file to analyze")
+                .help("Specify the GC log files, directories or glob patterns to analyze (- for stdin)")
                 .required(true)
-                .action(ArgAction::Set),
+                .num_args(1..)
+                .action(ArgAction::Append),
         )
         .arg(
             Arg::new("plot")
@@ -30,10 +32,10 @@ pub fn generate_cli_app() -> Command {
                 .long("mode")
                 .short('m')
                 .value_name("MODE")
-                .help("Rendering mode: heap, pause, or combined")
+                .help("Rendering mode: heap, pause, combined, or metaspace")
                 .required(false)
                 .default_value("combined")
-                .value_parser(["heap", "pause", "combined"])
+                .value_parser(["heap", "pause", "combined", "metaspace"])
                 .action(ArgAction::Set),
         )
         .arg(
@@ -51,4 +53,32 @@ pub fn generate_cli_app() -> Command {
                 .required(false)
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("units")
+                .long("units")
+                .short('u')
+                .value_name("UNIT")
+                .help("Scale every heap field in CSV, summary and charts: bytes, MB, GB, or regions")
+                .required(false)
+                .value_parser(HeapUnit::parse_arg)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("analyze")
+                .long("analyze")
+                .short('a')
+                .value_name("ANALYSIS")
+                .help("Print an analysis report: imbalance (worker load imbalance), cpu (CPU starvation / swapping), consistency (heap before/after GC blocks vs summary lines), humongous (humongous allocations and region size), tenuring (tenuring threshold and premature promotion), references (pauses dominated by reference processing)")
+                .required(false)
+                .value_parser(["imbalance", "cpu", "consistency", "humongous", "tenuring", "references"])
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("follow")
+                .long("follow")
+                .short('f')
+                .help("Keep the GC log open like tail -F and print a summary line per GC")
+                .required(false)
+                .action(ArgAction::SetTrue),
+        )
 }
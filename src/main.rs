@@ -1,44 +1,87 @@
 use clap::Parser;
 use csv::Writer;
-use gc_log_analyzer::{arg::*, util::*};
+use gc_log_analyzer::{arg::*, model::*, util::*};
+use std::path::Path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    let lines = read_lines(&args.input)?;
-    let gc_events = parse_gc_events(lines)?;
+    if args.follow {
+        let [path] = args.input.as_slice() else {
+            return Err("--follow takes exactly one log file".into());
+        };
+        follow_gc_log(Path::new(path))?;
+        return Ok(());
+    }
+
+    let mut lines = read_log_set(&args.input)?;
+    // 先頭行からログ形式とコレクタを判定し、対応するパーサに振り分ける
+    let head: Vec<String> = lines.by_ref().take(DETECT_LINES).collect();
+    let log_info = detect_log_info(&head);
+    let mut gc_log = parse_gc_log(head.into_iter().chain(lines), &log_info)?;
+    // --units 指定時はCSV・サマリ・グラフのヒープ量を同じ単位に揃える
+    let units = args.units.as_deref().map(HeapUnit::parse).transpose()?;
+    if let Some(unit) = units {
+        convert_heap_units(&mut gc_log.events, unit, &log_info.header)?;
+    }
+    let gc_events = &gc_log.events;
+    if gc_log.is_empty() {
+        eprintln!(
+            "⚠️ No GC events found (format: {:?}, collector: {:?})",
+            log_info.format, log_info.collector
+        );
+    }
 
     // CSV出力オプション対応
     if let Some(csv_path) = args.csv {
         let mut wtr = Writer::from_path(&csv_path)?;
-        for event in &gc_events {
+        for event in gc_events {
             wtr.serialize(event)?;
         }
         wtr.flush()?;
         println!("✅ Exported the CSV: {}", &csv_path);
     }
 
-    match args.mode.as_str() {
-        "heap" => {
-            draw_heap_chart(&gc_events, &args.plot)?;
-            println!("✅ Heap Graph saved: {}", &args.plot);
-        }
-        "pause" => {
-            draw_pause_chart(&gc_events, &args.plot)?;
-            println!("✅ Pause Graph saved: {}", &args.plot);
-        }
-        "combined" => {
-            draw_combined_chart(&gc_events, &args.plot)?;
-            println!("✅ Combined Graph saved: {}", &args.plot);
-        }
-        _ => {
-            eprintln!("❌ Invalid mode specified（heap, pause, combined）");
-        }
+    // グラフを描けなくても、サマリと分析レポートは出力する
+    let drawn = match args.mode.as_str() {
+        "heap" => draw_heap_chart(gc_events, &args.plot, units).map(|()| "Heap"),
+        "pause" => draw_pause_chart(gc_events, &args.plot).map(|()| "Pause"),
+        "combined" => draw_combined_chart(gc_events, &args.plot, units).map(|()| "Combined"),
+        "metaspace" => draw_metaspace_chart(gc_events, &args.plot, units).map(|()| "Metaspace"),
+        _ => Err("Invalid mode specified（heap, pause, combined, metaspace）".into()),
+    };
+    match drawn {
+        Ok(kind) => println!("✅ {} Graph saved: {}", kind, &args.plot),
+        Err(err) => eprintln!("❌ Failed to draw the graph: {}", err),
     }
 
     if args.summary {
-        let gc_type_counts = count_gc_types(&gc_events);
+        print_log_info(&log_info, units);
+        print_heap_summary(gc_events, units);
+        if let Some(exit) = &gc_log.exit {
+            print_exit_summary(exit, gc_events, units, log_info.header.region_size);
+        }
+        let gc_type_counts = count_gc_types(gc_events);
         print_gc_type_summary(&gc_type_counts);
+        print_gc_cause_summary(&count_gc_causes(gc_events));
+        print_young_phase_summary(&count_young_phases(gc_events));
+        print_evacuation_failure_summary(gc_events);
+        print_pause_phase_summary(gc_events);
+        print_concurrent_cycle_summary(&gc_log.cycles);
+    }
+
+    match args.analyze.as_deref() {
+        Some("imbalance") => print_worker_imbalance_report(gc_events),
+        Some("cpu") => print_cpu_time_report(gc_events),
+        Some("consistency") => print_heap_consistency_report(gc_events),
+        Some("humongous") => print_humongous_report(gc_events, log_info.header.region_size, units),
+        Some("tenuring") => print_tenuring_report(gc_events, log_info.header.region_size, units),
+        Some("references") => print_reference_report(gc_events),
+        Some(other) => eprintln!(
+            "❌ Invalid analysis specified: {}（imbalance, cpu, consistency, humongous, tenuring, references）",
+            other
+        ),
+        None => {}
     }
 
     Ok(())
@@ -0,0 +1,41 @@
+use crate::model::{GCEvent, GcPhase, JvmExitSummary};
+use chrono::{DateTime, FixedOffset};
+
+// G1 / CMS の並行サイクル (Concurrent Cycle)
+// Pause を伴わないので GCEvent とは別に、開始・終了時刻とフェーズごとの所要時間を保持する
+#[derive(Debug, Clone, Default)]
+pub struct ConcurrentCycle {
+    pub gc_id: Option<u64>,
+    pub start: Option<DateTime<FixedOffset>>,
+    pub end: Option<DateTime<FixedOffset>>,
+    pub start_uptime_secs: Option<f64>,
+    pub end_uptime_secs: Option<f64>,
+    // "Concurrent Cycle 37.466ms" の合計時間 (JDK 8 は開始・終了の uptime の差)
+    pub duration_ms: Option<f64>,
+    pub phases: Vec<GcPhase>,
+    // Concurrent Mark Abort / concurrent mode failure で中断された
+    pub aborted: bool,
+}
+
+// 解析結果: Pause 等のGCイベントと並行サイクル、JVM 終了時のヒープの状態
+#[derive(Debug, Clone, Default)]
+pub struct GcLog {
+    pub events: Vec<GCEvent>,
+    pub cycles: Vec<ConcurrentCycle>,
+    pub exit: Option<JvmExitSummary>,
+}
+
+impl GcLog {
+    pub fn extend(&mut self, other: GcLog) {
+        self.events.extend(other.events);
+        self.cycles.extend(other.cycles);
+        // ローテーションされた複数のログでは最後の JVM 終了時の状態を残す
+        if other.exit.is_some() {
+            self.exit = other.exit;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.cycles.is_empty()
+    }
+}
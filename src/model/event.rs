@@ -1,13 +1,33 @@
 use chrono::{DateTime, FixedOffset};
+use regex::Regex;
 use serde::Serialize;
+use std::sync::LazyLock;
 
 // GC Eventマッピング用の構造体の定義
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, Default)]
 pub struct GCEvent {
+    pub gc_id: Option<u64>,
     pub time: Option<DateTime<FixedOffset>>,
+    // JVM起動からの経過時間 (uptime / uptimemillis / uptimenanos)
+    pub uptime_secs: Option<f64>,
     pub gc_type: GcType,
+    pub gc_cause: Option<GcCause>,
+    // G1 の Pause Young の種類 (Normal / Concurrent Start / Prepare Mixed / Mixed)
+    pub young_phase: Option<YoungPhase>,
+    // G1 の退避失敗 (To-space exhausted / Evacuation Failure)
+    pub evacuation_failure: Option<EvacuationFailure>,
     pub has_pause: bool,
     pub pause_time_ms: Option<f64>,
+    // [gc,cpu] "User=0.08s Sys=0.01s Real=0.02s" / JDK 8 の "[Times: user=0.08 sys=0.01, real=0.02 secs]"
+    pub cpu_user_secs: Option<f64>,
+    pub cpu_sys_secs: Option<f64>,
+    pub cpu_real_secs: Option<f64>,
+    // 実効並列度 (user + sys) / real
+    pub parallelism: Option<f64>,
+    // サマリ行 (例: 307M->17M(6144M)) のヒープ使用量・コミット量 (bytes)
+    pub heap_used_before: Option<f64>,
+    pub heap_used_after: Option<f64>,
+    pub heap_committed: Option<f64>,
     pub eden_before: Option<f64>,
     pub eden_after: Option<f64>,
     pub eden_total: Option<f64>,
@@ -18,28 +38,557 @@ pub struct GCEvent {
     pub old_after: Option<f64>,
     pub humongous_before: Option<f64>,
     pub humongous_after: Option<f64>,
+    // [gc,humongous] / [gc,ergo] から分かった Humongous オブジェクトのサイズ (bytes, --units の対象外)
+    #[serde(skip)]
+    pub humongous_object_sizes: Vec<f64>,
+    // Parallel / Serial GC の世代ごとのサイズ (bytes)
+    pub young_gen_before: Option<f64>,
+    pub young_gen_after: Option<f64>,
+    pub young_gen_total: Option<f64>,
+    pub old_gen_before: Option<f64>,
+    pub old_gen_after: Option<f64>,
+    pub old_gen_total: Option<f64>,
+    // ZGC のヒープテーブル (Relocate End 時点, bytes)
+    pub heap_free: Option<f64>,
+    pub heap_live: Option<f64>,
+    pub heap_allocated: Option<f64>,
+    pub heap_garbage: Option<f64>,
+    pub heap_reclaimed: Option<f64>,
+    // Metaspace と Compressed Class Space (bytes)
+    pub metaspace_used_before: Option<f64>,
+    pub metaspace_used_after: Option<f64>,
+    pub metaspace_committed: Option<f64>,
+    pub metaspace_reserved: Option<f64>,
+    pub class_space_used_before: Option<f64>,
+    pub class_space_used_after: Option<f64>,
+    pub class_space_committed: Option<f64>,
+    // debug の Heap before/after GC ブロック (invocations は JVM 起動からの GC 回数, ヒープは bytes, young/survivors はリージョン数)
+    pub invocations_before: Option<u64>,
+    pub full_invocations_before: Option<u64>,
+    pub invocations_after: Option<u64>,
+    pub full_invocations_after: Option<u64>,
+    pub block_heap_used_before: Option<f64>,
+    pub block_heap_used_after: Option<f64>,
+    pub block_heap_total_before: Option<f64>,
+    pub block_heap_total_after: Option<f64>,
+    pub block_young_regions_before: Option<f64>,
+    pub block_young_regions_after: Option<f64>,
+    pub block_survivor_regions_before: Option<f64>,
+    pub block_survivor_regions_after: Option<f64>,
+    // [gc,age] / -XX:+PrintTenuringDistribution のテニュアリングしきい値と Survivor の目標サイズ (bytes)
+    pub tenuring_threshold: Option<u32>,
+    pub max_tenuring_threshold: Option<u32>,
+    pub desired_survivor_size: Option<f64>,
+    // "- age 1: 8386048 bytes" の年齢ごとの Survivor のバイト数
+    #[serde(skip)]
+    pub age_table: Vec<AgeBytes>,
+    // Reference Processing 全体の所要時間と、参照型ごとの件数 (Discovered) と所要時間 (ms)
+    pub ref_processing_ms: Option<f64>,
+    pub soft_refs: Option<u64>,
+    pub weak_refs: Option<u64>,
+    pub final_refs: Option<u64>,
+    pub phantom_refs: Option<u64>,
+    pub soft_ref_ms: Option<f64>,
+    pub weak_ref_ms: Option<f64>,
+    pub final_ref_ms: Option<f64>,
+    pub phantom_ref_ms: Option<f64>,
+    // [gc,stringtable] の String / Symbol テーブルの掃除の件数
+    pub strings_processed: Option<u64>,
+    pub strings_removed: Option<u64>,
+    pub symbols_processed: Option<u64>,
+    pub symbols_removed: Option<u64>,
+    // Heap before/after GC ブロックとサマリ行・リージョン行の食い違い
+    #[serde(skip)]
+    pub heap_block_issues: Vec<String>,
+    // サイクル内の各フェーズ (ZGC の Pause Mark Start / Concurrent Mark 等)
+    #[serde(skip)]
+    pub phases: Vec<GcPhase>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+impl GCEvent {
+    pub fn set_cpu_times(&mut self, user: f64, sys: f64, real: f64) {
+        self.cpu_user_secs = Some(user);
+        self.cpu_sys_secs = Some(sys);
+        self.cpu_real_secs = Some(real);
+        // real は 10ms 単位で丸められるので 0.00s のときは求めず、比率も小数2桁に丸める
+        self.parallelism = (real > 0.0).then(|| ((user + sys) / real * 100.0).round() / 100.0);
+    }
+
+    // ワーカーごとの統計を持つフェーズ (入れ子の内側を含む)
+    pub fn worker_phases(&self) -> Vec<&GcPhase> {
+        fn collect<'a>(phases: &'a [GcPhase], out: &mut Vec<&'a GcPhase>) {
+            for phase in phases {
+                if phase.workers.is_some() {
+                    out.push(phase);
+                }
+                collect(&phase.children, out);
+            }
+        }
+        let mut out = Vec::new();
+        collect(&self.phases, &mut out);
+        out
+    }
+}
+
+// Survivor 領域の年齢ごとのバイト数
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct AgeBytes {
+    pub age: u32,
+    pub bytes: f64,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct GcPhase {
+    pub name: String,
+    pub duration_ms: f64,
+    // G1 の [gc,phases] のように入れ子で出力される内訳
+    // (例: Evacuate Collection Set の下の Ext Root Scanning / Object Copy)
+    pub children: Vec<GcPhase>,
+    // "Object Copy (ms): Min: ..., Avg: ..., Max: ..." のような並列フェーズのワーカー統計
+    pub workers: Option<WorkerStats>,
+}
+
+impl GcPhase {
+    pub fn new(name: &str, duration_ms: f64) -> GcPhase {
+        GcPhase {
+            name: name.to_string(),
+            duration_ms,
+            ..Default::default()
+        }
+    }
+
+    // ワーカー統計を持つフェーズは平均を所要時間とする
+    pub fn with_workers(name: &str, workers: WorkerStats) -> GcPhase {
+        GcPhase {
+            name: name.to_string(),
+            duration_ms: workers.avg,
+            workers: Some(workers),
+            ..Default::default()
+        }
+    }
+
+    // Generational ZGC では "Y: Pause Mark Start" のように世代の接頭辞 (y/Y/O) が付く
+    pub fn is_pause(&self) -> bool {
+        self.name.starts_with("Pause") || self.name.contains(": Pause")
+    }
+
+    // "GC Worker Total" は同じ段の並列フェーズの合計
+    pub fn is_aggregate(&self) -> bool {
+        self.name == "GC Worker Total"
+    }
+}
+
+// 並列フェーズのワーカーごとの所要時間 (ms)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WorkerStats {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    pub diff: f64,
+    pub sum: f64,
+    // JDK 8 の行には無いので親の "GC Workers: N" から補う
+    pub workers: Option<u32>,
+}
+
+impl WorkerStats {
+    // 最も遅いワーカーが平均の何倍かかったか
+    pub fn skew(&self) -> Option<f64> {
+        (self.avg > 0.0).then(|| self.max / self.avg)
+    }
+}
+
+// depth 段目のフェーズとして、直前に追加された親フェーズの子に加える
+// (親が無い場合はその段にそのまま追加する)
+pub fn push_phase(phases: &mut Vec<GcPhase>, depth: usize, phase: GcPhase) {
+    match phases.last_mut() {
+        Some(parent) if depth > 0 => push_phase(&mut parent.children, depth - 1, phase),
+        _ => phases.push(phase),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Default)]
 pub enum GcType {
     Young,
+    // G1 の Pause Young (Mixed) / Pause Young (Prepare Mixed)
+    Mixed,
+    PrepareMixed,
+    // CMS の並行サイクルを開始する Pause Initial Mark
+    InitialMark,
+    // 並行マーキング中の G1 / CMS の Pause Remark と G1 の Pause Cleanup
+    Remark,
+    Cleanup,
     Full,
     Concurrent,
+    // Generational ZGC (JDK 21+)
+    Minor,
+    Major,
+    // Shenandoah の Degenerated GC (並行GCが間に合わずSTWで続行した)
+    Degenerated,
+    // Shenandoah の並行サイクルを開始する Pause Init Mark と、
+    // サイクル中の Pause Final Mark / Pause Init Update Refs / Pause Final Update Refs
+    InitMark,
+    FinalMark,
+    InitUpdateRefs,
+    FinalUpdateRefs,
+    #[default]
     Unknown,
 }
 
 pub fn detect_gc_type(line: &str) -> GcType {
-    if line.contains("Pause Young")
-        || line.contains("Evacuation Pause")
-        || line.contains("G1 Humongous Allocation")
-    {
+    if line.contains("Pause Degenerated GC") {
+        GcType::Degenerated
+    } else if line.contains("Full GC") || line.contains("Pause Full") {
+        GcType::Full
+    } else if line.contains("Pause Young (Mixed)") {
+        GcType::Mixed
+    } else if line.contains("Pause Young (Prepare Mixed)") {
+        GcType::PrepareMixed
+    } else if line.contains("Pause Young") {
+        // "(G1 Humongous Allocation)" などはGCの原因であり種別ではない
         GcType::Young
-    } else if line.contains("Full GC") || line.contains("Pause Full") || line.contains("G1 Full GC")
+    } else if line.contains("Pause Initial Mark") {
+        GcType::InitialMark
+    } else if line.contains("Pause Init Mark") {
+        GcType::InitMark
+    } else if line.contains("Pause Final Mark") {
+        GcType::FinalMark
+    } else if line.contains("Pause Init Update Refs") {
+        GcType::InitUpdateRefs
+    } else if line.contains("Pause Final Update Refs") {
+        GcType::FinalUpdateRefs
+    } else if line.contains("Pause Remark") {
+        GcType::Remark
+    } else if line.contains("Pause Cleanup") {
+        GcType::Cleanup
+    } else if line.contains("Minor Collection") {
+        GcType::Minor
+    } else if line.contains("Major Collection") {
+        GcType::Major
+    } else if line.contains("Garbage Collection (") {
+        // 非世代別ZGCのサイクルは並行処理が主体
+        GcType::Concurrent
+    } else if line.contains("GC concurrent")
+        || line.contains("Concurrent Cycle")
+        || line.contains("Concurrent Undo Cycle")
+        || line.contains("Concurrent reset")
     {
-        GcType::Full
-    } else if line.contains("GC concurrent") || line.contains("Concurrent Cycle") {
         GcType::Concurrent
     } else {
         GcType::Unknown
     }
 }
+
+// GCの原因 (Pause Young や Garbage Collection の後ろの括弧内)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum GcCause {
+    EvacuationPause,
+    HumongousAllocation,
+    MetadataGcThreshold,
+    MetadataGcClearSoftReferences,
+    SystemGc,
+    AllocationFailure,
+    Ergonomics,
+    GcLocker,
+    PeriodicCollection,
+    PreventiveCollection,
+    CompactionPause,
+    HeapDump,
+    HeapInspection,
+    CodeCacheGcThreshold,
+    LastDitchCollection,
+    // ZGC / Shenandoah
+    Warmup,
+    AllocationRate,
+    AllocationStall,
+    HighUsage,
+    Proactive,
+    Timer,
+    // Shenandoah の "Trigger: Free (965M) is below minimum threshold (409M)" 等
+    MinimumFreeThreshold,
+    LearningThreshold,
+    // CMS
+    CmsInitialMark,
+    CmsFinalRemark,
+    Other,
+}
+
+impl GcCause {
+    pub fn from_text(text: &str) -> GcCause {
+        match text {
+            "G1 Evacuation Pause" => GcCause::EvacuationPause,
+            "G1 Humongous Allocation" => GcCause::HumongousAllocation,
+            "Metadata GC Threshold" => GcCause::MetadataGcThreshold,
+            "Metadata GC Clear Soft References" => GcCause::MetadataGcClearSoftReferences,
+            "Allocation Failure" => GcCause::AllocationFailure,
+            "Ergonomics" => GcCause::Ergonomics,
+            "GCLocker Initiated GC" => GcCause::GcLocker,
+            "G1 Periodic Collection" => GcCause::PeriodicCollection,
+            "G1 Preventive Collection" => GcCause::PreventiveCollection,
+            "G1 Compaction Pause" => GcCause::CompactionPause,
+            "Heap Dump Initiated GC" => GcCause::HeapDump,
+            "Heap Inspection Initiated GC" => GcCause::HeapInspection,
+            "CodeCache GC Threshold" => GcCause::CodeCacheGcThreshold,
+            "Last ditch collection" => GcCause::LastDitchCollection,
+            "Warmup" => GcCause::Warmup,
+            "Allocation Rate" => GcCause::AllocationRate,
+            "Allocation Stall" => GcCause::AllocationStall,
+            "High Usage" => GcCause::HighUsage,
+            "Proactive" => GcCause::Proactive,
+            "Timer" => GcCause::Timer,
+            "CMS Initial Mark" => GcCause::CmsInitialMark,
+            "CMS Final Remark" => GcCause::CmsFinalRemark,
+            // "System.gc()" は括弧を含むので前方一致で判定する
+            _ if text.starts_with("System.gc") => GcCause::SystemGc,
+            _ => GcCause::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum YoungPhase {
+    Normal,
+    // JDK 11 以前の "Initial Mark" を含む
+    ConcurrentStart,
+    PrepareMixed,
+    Mixed,
+}
+
+// 退避先のリージョンが確保できず、オブジェクトをその場に残した Young / Mixed の Pause
+// Full GC の前兆なので、1つの Pause に複数出力されたときはより具体的なものを残す (Ord の順)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum EvacuationFailure {
+    // [gc,phases] の "Evacuation Failure: 1.2ms" のように原因が分からないもの (JDK 17-19 の "(Evacuation Failure)" を含む)
+    Failure,
+    // JDK 16 以前の "To-space exhausted" / JDK 8 の "(to-space exhausted)"
+    ToSpaceExhausted,
+    // JDK 20+ の "(Evacuation Failure: Allocation)" / "(Evacuation Failure: Pinned)" / 両方
+    Allocation,
+    Pinned,
+    AllocationPinned,
+}
+
+// "Pause Young (Normal) (G1 Evacuation Pause) (Evacuation Failure: Allocation / Pinned) ..."
+static RE_EVACUATION_FAILURE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Evacuation Failure(?:: (Allocation|Pinned)(?: / (Allocation|Pinned))?)?").unwrap()
+});
+static RE_TO_SPACE_EXHAUSTED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)to-space (?:exhausted|overflow)").unwrap());
+
+pub fn detect_evacuation_failure(line: &str) -> Option<EvacuationFailure> {
+    if RE_TO_SPACE_EXHAUSTED.is_match(line) {
+        return Some(EvacuationFailure::ToSpaceExhausted);
+    }
+    let caps = RE_EVACUATION_FAILURE.captures(line)?;
+    Some(match (caps.get(1).map(|m| m.as_str()), caps.get(2)) {
+        (Some(_), Some(_)) => EvacuationFailure::AllocationPinned,
+        (Some("Allocation"), None) => EvacuationFailure::Allocation,
+        (Some(_), None) => EvacuationFailure::Pinned,
+        (None, _) => EvacuationFailure::Failure,
+    })
+}
+
+// [gc,humongous] JDK 17+: "Humongous region 1234 (object size 8388624 @[0x...]) remset 0 ..."
+// JDK 11: "Dead humongous region 1234 object size 8388624 start 0x... with remset 0 ..."
+// [gc,ergo] "Request concurrent cycle initiation ... allocation request: 8388624B threshold: ... source: concurrent humongous allocation"
+static RE_HUMONGOUS_OBJECT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:[Hh]umongous region \d+ \(?object size (\d+)|allocation request: (\d+) ?(?:B|bytes).*source: concurrent humongous allocation)",
+    )
+    .unwrap()
+});
+
+pub fn detect_humongous_object_size(line: &str) -> Option<f64> {
+    let caps = RE_HUMONGOUS_OBJECT.captures(line)?;
+    caps.get(1).or(caps.get(2))?.as_str().parse().ok()
+}
+
+// "Pause Young (Concurrent Start) (G1 Humongous Allocation)"
+static RE_G1_YOUNG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Pause Young \((Normal|Concurrent Start|Initial Mark|Prepare Mixed|Mixed)\)(?: \(([^)]+)\))?")
+        .unwrap()
+});
+// "Pause Full (System.gc())", "Garbage Collection (Warmup)", JDK 8 の "[GC (Allocation Failure)"
+static RE_CAUSE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:Pause Young|Pause Full|Full GC|GC pause|\[GC|Collection) \(([^)]+)\)").unwrap()
+});
+// JDK 8 の G1: "[GC pause (G1 Evacuation Pause) (young) (initial-mark)"
+static RE_LEGACY_YOUNG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\((young|mixed)\)( \(initial-mark\))?").unwrap());
+
+pub fn detect_gc_cause(line: &str) -> Option<GcCause> {
+    if let Some(caps) = RE_G1_YOUNG.captures(line) {
+        return caps.get(2).map(|m| GcCause::from_text(m.as_str()));
+    }
+    RE_CAUSE
+        .captures(line)
+        .map(|caps| GcCause::from_text(&caps[1]))
+}
+
+pub fn detect_young_phase(line: &str) -> Option<YoungPhase> {
+    if let Some(caps) = RE_G1_YOUNG.captures(line) {
+        return Some(match &caps[1] {
+            "Normal" => YoungPhase::Normal,
+            "Concurrent Start" | "Initial Mark" => YoungPhase::ConcurrentStart,
+            "Prepare Mixed" => YoungPhase::PrepareMixed,
+            _ => YoungPhase::Mixed,
+        });
+    }
+    if line.contains("GC pause")
+        && let Some(caps) = RE_LEGACY_YOUNG.captures(line)
+    {
+        return Some(match (&caps[1], caps.get(2)) {
+            ("young", Some(_)) => YoungPhase::ConcurrentStart,
+            ("young", None) => YoungPhase::Normal,
+            _ => YoungPhase::Mixed,
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_g1_pause_types() {
+        let cases = [
+            (
+                "GC(2) Pause Young (Concurrent Start) (G1 Humongous Allocation)",
+                GcType::Young,
+            ),
+            (
+                "GC(30) Pause Young (Prepare Mixed) (G1 Evacuation Pause)",
+                GcType::PrepareMixed,
+            ),
+            (
+                "GC(31) Pause Young (Mixed) (G1 Evacuation Pause)",
+                GcType::Mixed,
+            ),
+            ("GC(3) Pause Remark 21M->21M(6144M) 3.618ms", GcType::Remark),
+            (
+                "GC(3) Pause Cleanup 21M->21M(6144M) 0.257ms",
+                GcType::Cleanup,
+            ),
+            ("GC(3) Concurrent Cycle 37.466ms", GcType::Concurrent),
+            ("GC(3) Finalize Marking 0.262ms", GcType::Unknown),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(detect_gc_type(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn detects_evacuation_failure_kinds() {
+        let cases = [
+            // JDK 9-16
+            (
+                "[7.123s][info][gc] GC(12) To-space exhausted",
+                Some(EvacuationFailure::ToSpaceExhausted),
+            ),
+            // JDK 8
+            (
+                "2025-04-11T09:50:21.912+0900: 4.606: [GC pause (G1 Evacuation Pause) (young) (to-space exhausted), 0.0512340 secs]",
+                Some(EvacuationFailure::ToSpaceExhausted),
+            ),
+            (
+                "[7.123s][info][gc,phases] GC(12)     Evacuation Failure: 1.2ms",
+                Some(EvacuationFailure::Failure),
+            ),
+            // JDK 17+
+            (
+                "[7.123s][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) (Evacuation Failure: Allocation) 6010M->5987M(6144M) 45.107ms",
+                Some(EvacuationFailure::Allocation),
+            ),
+            (
+                "[7.123s][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) (Evacuation Failure: Pinned) 2010M->1987M(6144M) 12.310ms",
+                Some(EvacuationFailure::Pinned),
+            ),
+            (
+                "[7.123s][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) (Evacuation Failure: Allocation / Pinned) 6010M->5990M(6144M) 48.002ms",
+                Some(EvacuationFailure::AllocationPinned),
+            ),
+            (
+                "[7.123s][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) 307M->17M(6144M) 21.425ms",
+                None,
+            ),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(detect_evacuation_failure(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn detects_gc_cause_and_young_phase() {
+        let cases = [
+            (
+                "GC(19) Pause Young (Concurrent Start) (G1 Humongous Allocation)",
+                Some(GcCause::HumongousAllocation),
+                Some(YoungPhase::ConcurrentStart),
+            ),
+            (
+                "GC(4) Pause Young (Normal) (G1 Evacuation Pause) 390M->44M(6144M) 58.748ms",
+                Some(GcCause::EvacuationPause),
+                Some(YoungPhase::Normal),
+            ),
+            (
+                "GC(9) Pause Full (System.gc()) 489M->60M(6144M) 148.023ms",
+                Some(GcCause::SystemGc),
+                None,
+            ),
+            (
+                "GC(2) Pause Young (Concurrent Start) (Metadata GC Threshold)",
+                Some(GcCause::MetadataGcThreshold),
+                Some(YoungPhase::ConcurrentStart),
+            ),
+            // JDK 11 以前の "Initial Mark"
+            (
+                "GC(2) Pause Young (Initial Mark) (Metadata GC Threshold)",
+                Some(GcCause::MetadataGcThreshold),
+                Some(YoungPhase::ConcurrentStart),
+            ),
+            // JDK 8
+            (
+                "2.127: [GC pause (Metadata GC Threshold) (young) (initial-mark), 0.0133340 secs]",
+                Some(GcCause::MetadataGcThreshold),
+                Some(YoungPhase::ConcurrentStart),
+            ),
+            (
+                "2.127: [GC pause (G1 Evacuation Pause) (mixed), 0.0133340 secs]",
+                Some(GcCause::EvacuationPause),
+                Some(YoungPhase::Mixed),
+            ),
+            (
+                "1.234: [Full GC (System.gc()) [PSYoungGen: 4096K->0K(141824K)]",
+                Some(GcCause::SystemGc),
+                None,
+            ),
+            (
+                "GC(7) Garbage Collection (Allocation Rate) 1234M(30%)->456M(11%)",
+                Some(GcCause::AllocationRate),
+                None,
+            ),
+            ("GC(3) Pause Remark 21M->21M(6144M) 3.618ms", None, None),
+        ];
+        for (line, cause, phase) in cases {
+            assert_eq!(detect_gc_cause(line), cause, "{}", line);
+            assert_eq!(detect_young_phase(line), phase, "{}", line);
+        }
+    }
+
+    #[test]
+    fn maps_unknown_cause_text_to_other() {
+        assert_eq!(GcCause::from_text("System.gc()"), GcCause::SystemGc);
+        assert_eq!(
+            GcCause::from_text("Metadata GC Threshold"),
+            GcCause::MetadataGcThreshold
+        );
+        assert_eq!(
+            GcCause::from_text("WhiteBox Initiated Young GC"),
+            GcCause::Other
+        );
+        assert_eq!(
+            detect_gc_cause("GC(5) Pause Young (Normal) (JvmtiEnv ForceGarbageCollection)"),
+            Some(GcCause::Other)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::util::heap_block::{parse_block_regions, parse_space_usage};
+use crate::util::metaspace::parse_metaspace_detail;
+use chrono::{DateTime, FixedOffset};
+
+// JVM 終了時に [gc,heap,exit] (JDK 8 は末尾の "Heap") に出力されるヒープの状態
+#[derive(Debug, Clone, Default)]
+pub struct JvmExitSummary {
+    pub time: Option<DateTime<FixedOffset>>,
+    pub uptime_secs: Option<f64>,
+    // ヒープ全体 (世代別のコレクタは各世代の合計, bytes)
+    pub heap_total: Option<f64>,
+    pub heap_used: Option<f64>,
+    // G1 の young / survivors のリージョン数
+    pub young_regions: Option<f64>,
+    pub survivor_regions: Option<f64>,
+    // Metaspace と Compressed Class Space (bytes)
+    pub metaspace_used: Option<f64>,
+    pub metaspace_committed: Option<f64>,
+    pub metaspace_reserved: Option<f64>,
+    pub class_space_used: Option<f64>,
+    pub class_space_committed: Option<f64>,
+    pub class_space_reserved: Option<f64>,
+}
+
+impl JvmExitSummary {
+    // "Heap" に続くブロックの1行を反映する
+    pub fn apply_line(&mut self, line: &str) {
+        if let Some((total, used)) = parse_space_usage(line) {
+            *self.heap_total.get_or_insert(0.0) += total;
+            *self.heap_used.get_or_insert(0.0) += used;
+        } else if let Some((young, survivors)) = parse_block_regions(line) {
+            self.young_regions = Some(young);
+            self.survivor_regions = Some(survivors);
+        } else if let Some(detail) = parse_metaspace_detail(line) {
+            if detail.class_space {
+                self.class_space_used = detail.used;
+                self.class_space_committed = detail.committed;
+                self.class_space_reserved = detail.reserved;
+            } else {
+                self.metaspace_used = detail.used;
+                self.metaspace_committed = detail.committed;
+                self.metaspace_reserved = detail.reserved;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{parse_gc_events, parse_legacy_gc_events};
+
+    const KB: f64 = 1024.0;
+
+    fn lines(text: &str) -> impl Iterator<Item = String> + '_ {
+        text.lines().map(String::from)
+    }
+
+    fn assert_heap(exit: Option<JvmExitSummary>, total_kb: f64, used_kb: f64) {
+        let exit = exit.unwrap();
+        assert_eq!(exit.heap_total, Some(total_kb * KB));
+        assert_eq!(exit.heap_used, Some(used_kb * KB));
+    }
+
+    #[test]
+    fn reads_g1_heap() {
+        let log = parse_gc_events(lines(include_str!("../../sample/sample_gc.log"))).unwrap();
+        assert_heap(log.exit.clone(), 6291456.0, 3098663.0);
+        let exit = log.exit.unwrap();
+        assert_eq!(exit.young_regions, Some(273.0));
+        assert_eq!(exit.survivor_regions, Some(1.0));
+        assert_eq!(exit.metaspace_used, Some(324404.0 * KB));
+        assert_eq!(exit.class_space_reserved, Some(1048576.0 * KB));
+    }
+
+    #[test]
+    fn sums_parallel_generations() {
+        let log =
+            parse_legacy_gc_events(lines(include_str!("../../sample/sample_parallel_jdk8.log")))
+                .unwrap();
+        // PSYoungGen + ParOldGen
+        assert_heap(log.exit, 157184.0 + 349696.0, 52310.0 + 98765.0);
+    }
+
+    #[test]
+    fn sums_cms_generations() {
+        let log = parse_legacy_gc_events(lines(include_str!("../../sample/sample_cms_jdk8.log")))
+            .unwrap();
+        // par new generation + concurrent mark-sweep generation
+        assert_heap(log.exit, 157248.0 + 349568.0, 84312.0 + 158922.0);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::util::parser::parse_size;
+use regex::Regex;
+use std::sync::LazyLock;
+
+// 起動時に出力される JVM とヒープの設定
+#[derive(Debug, Clone, Default)]
+pub struct JvmHeader {
+    // G1 / Shenandoah のリージョンサイズ (bytes)
+    pub region_size: Option<f64>,
+    // ヒープの最小・初期・最大サイズ (bytes)
+    pub min_heap: Option<f64>,
+    pub initial_heap: Option<f64>,
+    pub max_heap: Option<f64>,
+    // "Zero based" / "Enabled (Zero based)" / "Disabled" など
+    pub compressed_oops: Option<String>,
+    // STW フェーズの並列ワーカー数と並行フェーズのワーカー数
+    pub parallel_workers: Option<u32>,
+    pub concurrent_workers: Option<u32>,
+}
+
+// G1: "Heap region size: 4M" / JDK 17+ と Shenandoah: "Heap Region Size: 4M"
+static RE_REGION_SIZE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Heap [Rr]egion [Ss]ize: (\d+)([KMGT]?)B?\b").unwrap());
+// debug の Heap before GC ブロック: "region size 4096K, 76 young (311296K), ..."
+static RE_BLOCK_REGION_SIZE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"region size (\d+)([KMG]), ").unwrap());
+// JDK 11-16 debug: "Minimum heap 6442450944  Initial heap 6442450944  Maximum heap 6442450944"
+static RE_HEAP_SIZES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Minimum heap (\d+)\s+Initial heap (\d+)\s+Maximum heap (\d+)").unwrap()
+});
+// JDK 17+ [gc,init]: "Heap Min Capacity: 8M" / ZGC: "Max Capacity: 4096M"
+// (ZGC のGCごとの "Min Capacity: 8M(0%)" は対象外)
+static RE_HEAP_CAPACITY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:Heap )?(Min|Initial|Max) Capacity: (\d+)([KMGT]?)B?\s*$").unwrap()
+});
+// JDK 11-16: "Compressed Oops mode: Zero based, Oop shift amount: 3"
+// JDK 17+: "Compressed Oops: Enabled (Zero based)"
+static RE_COMPRESSED_OOPS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Compressed Oops(?: mode)?: ([^,]+?)(?:,|$)").unwrap());
+// JDK 17+ [gc,init]: "Parallel Workers: 4" / "Concurrent Workers: 1" / ZGC: "Runtime Workers: 1"
+static RE_WORKERS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|\] )(Parallel|Concurrent|GC) Workers: (\d+)").unwrap());
+// 起動時の情報が無い場合: "GC(0) Using 4 workers of 4 for evacuation" / JDK 8: "[Parallel Time: 19.4 ms, GC Workers: 4]"
+static RE_USING_WORKERS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Using \d+ workers of (\d+) for|GC Workers: (\d+)\]").unwrap());
+// JDK 8 の "CommandLine flags: -XX:G1HeapRegionSize=4194304 -XX:InitialHeapSize=6442450944 ..."
+static RE_FLAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"-XX:([+-]?)(\w+)(?:=(\S+))?").unwrap());
+
+pub fn detect_jvm_header(lines: &[String]) -> JvmHeader {
+    let mut header = JvmHeader::default();
+    for line in lines {
+        if line.starts_with("CommandLine flags:") {
+            apply_flags(&mut header, line);
+            continue;
+        }
+        if let Some(caps) = RE_REGION_SIZE.captures(line) {
+            header.region_size = parse_size(&caps[1], &caps[2]);
+        } else if header.region_size.is_none()
+            && let Some(caps) = RE_BLOCK_REGION_SIZE.captures(line)
+        {
+            header.region_size = parse_size(&caps[1], &caps[2]);
+        }
+        if let Some(caps) = RE_HEAP_SIZES.captures(line) {
+            header.min_heap = caps[1].parse().ok();
+            header.initial_heap = caps[2].parse().ok();
+            header.max_heap = caps[3].parse().ok();
+        }
+        if let Some(caps) = RE_HEAP_CAPACITY.captures(line) {
+            let size = parse_size(&caps[2], &caps[3]);
+            match &caps[1] {
+                "Min" => header.min_heap = size,
+                "Initial" => header.initial_heap = size,
+                _ => header.max_heap = size,
+            }
+        }
+        if header.compressed_oops.is_none()
+            && let Some(caps) = RE_COMPRESSED_OOPS.captures(line)
+        {
+            header.compressed_oops = Some(caps[1].trim().to_string());
+        }
+        if let Some(caps) = RE_WORKERS.captures(line) {
+            let workers = caps[2].parse().ok();
+            match &caps[1] {
+                "Concurrent" => header.concurrent_workers = workers,
+                _ => header.parallel_workers = workers,
+            }
+        } else if header.parallel_workers.is_none()
+            && let Some(caps) = RE_USING_WORKERS.captures(line)
+        {
+            header.parallel_workers = caps
+                .get(1)
+                .or(caps.get(2))
+                .and_then(|m| m.as_str().parse().ok());
+        }
+    }
+    header
+}
+
+fn apply_flags(header: &mut JvmHeader, line: &str) {
+    for caps in RE_FLAG.captures_iter(line) {
+        let value = caps.get(3).map(|m| m.as_str());
+        match (&caps[2], value) {
+            ("G1HeapRegionSize" | "ShenandoahRegionSize", Some(value)) => {
+                header.region_size = value.parse().ok()
+            }
+            ("InitialHeapSize", Some(value)) => header.initial_heap = value.parse().ok(),
+            ("MaxHeapSize", Some(value)) => header.max_heap = value.parse().ok(),
+            ("MinHeapSize", Some(value)) => header.min_heap = value.parse().ok(),
+            ("ParallelGCThreads", Some(value)) => header.parallel_workers = value.parse().ok(),
+            ("ConcGCThreads", Some(value)) => header.concurrent_workers = value.parse().ok(),
+            ("UseCompressedOops", None) => {
+                header.compressed_oops = Some(
+                    if &caps[1] == "+" {
+                        "Enabled"
+                    } else {
+                        "Disabled"
+                    }
+                    .to_string(),
+                )
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::units::MB;
+
+    fn header_of(text: &str) -> JvmHeader {
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        detect_jvm_header(&lines)
+    }
+
+    #[test]
+    fn reads_unified_g1_header() {
+        let header = header_of(include_str!("../../sample/sample_gc.log"));
+        assert_eq!(header.region_size, Some(4.0 * MB));
+        assert_eq!(header.min_heap, Some(6442450944.0));
+        assert_eq!(header.initial_heap, Some(6442450944.0));
+        assert_eq!(header.max_heap, Some(6442450944.0));
+        assert_eq!(header.compressed_oops.as_deref(), Some("Zero based"));
+        // 起動時のワーカー数が無いので "Using 4 workers of 4 for evacuation" から
+        assert_eq!(header.parallel_workers, Some(4));
+        assert_eq!(header.concurrent_workers, None);
+    }
+
+    #[test]
+    fn reads_jdk17_init_lines() {
+        let header = header_of(
+            "[0.010s][info][gc,init] Heap Region Size: 2M
+[0.010s][info][gc,init] Heap Min Capacity: 8M
+[0.010s][info][gc,init] Heap Initial Capacity: 256M
+[0.010s][info][gc,init] Heap Max Capacity: 4G
+[0.010s][info][gc,init] Compressed Oops: Enabled (Zero based)
+[0.010s][info][gc,init] Parallel Workers: 4
+[0.010s][info][gc,init] Concurrent Workers: 1
+[1.200s][info][gc,task] GC(0) Using 2 workers of 4 for evacuation",
+        );
+        assert_eq!(header.region_size, Some(2.0 * MB));
+        assert_eq!(header.min_heap, Some(8.0 * MB));
+        assert_eq!(header.initial_heap, Some(256.0 * MB));
+        assert_eq!(header.max_heap, Some(4096.0 * MB));
+        assert_eq!(
+            header.compressed_oops.as_deref(),
+            Some("Enabled (Zero based)")
+        );
+        assert_eq!(header.parallel_workers, Some(4));
+        assert_eq!(header.concurrent_workers, Some(1));
+    }
+
+    #[test]
+    fn reads_other_collectors() {
+        let header = header_of(include_str!("../../sample/sample_shenandoah.log"));
+        assert_eq!(header.region_size, Some(2.0 * MB));
+
+        // ZGC の GC ごとの "Min Capacity: 8M(0%)" はヘッダとして読まない
+        let header = header_of(include_str!("../../sample/sample_zgc_generational.log"));
+        assert_eq!(header.region_size, None);
+        assert_eq!(header.min_heap, Some(8.0 * MB));
+        assert_eq!(header.initial_heap, Some(256.0 * MB));
+        assert_eq!(header.max_heap, Some(4096.0 * MB));
+    }
+
+    #[test]
+    fn reads_jdk8_command_line_flags() {
+        let header = header_of(include_str!("../../sample/sample_gc_jdk8.log"));
+        assert_eq!(header.region_size, Some(4.0 * MB));
+        assert_eq!(header.initial_heap, Some(6442450944.0));
+        assert_eq!(header.max_heap, Some(6442450944.0));
+        assert_eq!(header.compressed_oops.as_deref(), Some("Enabled"));
+        // "[Parallel Time: 19.4 ms, GC Workers: 4]"
+        assert_eq!(header.parallel_workers, Some(4));
+
+        let header = header_of(
+            "CommandLine flags: -XX:-UseCompressedOops -XX:ParallelGCThreads=8 -XX:ConcGCThreads=2",
+        );
+        assert_eq!(header.compressed_oops.as_deref(), Some("Disabled"));
+        assert_eq!(header.parallel_workers, Some(8));
+        assert_eq!(header.concurrent_workers, Some(2));
+    }
+}
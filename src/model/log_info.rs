@@ -0,0 +1,245 @@
+use crate::model::header::{JvmHeader, detect_jvm_header};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+// ログ形式 (JDK 9 以降の統合ロギング / JDK 8 の PrintGCDetails)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Default)]
+pub enum LogFormat {
+    Unified,
+    Jdk8,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Default)]
+pub enum Collector {
+    G1,
+    Z,
+    Shenandoah,
+    Parallel,
+    Serial,
+    Cms,
+    #[default]
+    Unknown,
+}
+
+// ファイル先頭から検出したログの種類
+#[derive(Debug, Clone, Default)]
+pub struct LogInfo {
+    pub format: LogFormat,
+    pub collector: Collector,
+    pub jvm_version: Option<String>,
+    pub header: JvmHeader,
+}
+
+// 先頭行の判定に使う行数
+pub const DETECT_LINES: usize = 200;
+
+// "2.127: [GC ..." / "2025-04-11T09:50:19.433+0900: 2.127: [GC ..." / "2025-04-11T09:50:19.433+0900: [GC ..."
+// タイムスタンプ無しの "[GC (Allocation Failure) ..." / "[Full GC (Ergonomics) ..."
+static RE_JDK8_RECORD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:\S+: )?\d+\.\d+: \[|^\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{3}[+-]\d{4}: \[|^\[(?:Full )?GC[ (]",
+    )
+    .unwrap()
+});
+static RE_UNIFIED_DECORATOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[[^\]]*\].*\[gc").unwrap());
+// 統合ロギング: "[gc,init] Version: 17.0.9+9 (release)"
+// JDK 8: "Java HotSpot(TM) 64-Bit Server VM (25.401-b10) for linux-amd64 JRE (1.8.0_401-b10), ..."
+static RE_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Version: (\S+)|JRE \(([^)]+)\)").unwrap());
+
+pub fn detect_log_info(lines: &[String]) -> LogInfo {
+    let mut info = LogInfo::default();
+
+    for line in lines {
+        if info.jvm_version.is_none()
+            && let Some(caps) = RE_VERSION.captures(line)
+        {
+            info.jvm_version = caps.get(1).or(caps.get(2)).map(|m| m.as_str().to_string());
+        }
+
+        if info.format == LogFormat::Unknown {
+            if line.starts_with("CommandLine flags:")
+                || line.starts_with("Memory: ")
+                || RE_JDK8_RECORD.is_match(line)
+            {
+                info.format = LogFormat::Jdk8;
+            } else if RE_UNIFIED_DECORATOR.is_match(line) || line.contains("GC(") {
+                info.format = LogFormat::Unified;
+            }
+        }
+
+        if info.collector == Collector::Unknown {
+            info.collector = detect_collector(line);
+        }
+    }
+
+    if info.collector == Collector::Unknown {
+        info.collector = lines
+            .iter()
+            .map(|line| guess_collector(line))
+            .find(|collector| *collector != Collector::Unknown)
+            .unwrap_or_default();
+    }
+
+    info.header = detect_jvm_header(lines);
+
+    // JDK 8 でコレクタ指定が無い場合のデフォルトは Parallel
+    if info.format == LogFormat::Jdk8 && info.collector == Collector::Unknown {
+        info.collector = Collector::Parallel;
+    }
+    info
+}
+
+// "Using G1" などの起動時メッセージ・JVMオプションから判定する
+fn detect_collector(line: &str) -> Collector {
+    if line.contains("Using G1") || line.contains("-XX:+UseG1GC") {
+        Collector::G1
+    } else if line.contains("Using The Z Garbage Collector") || line.contains("-XX:+UseZGC") {
+        Collector::Z
+    } else if line.contains("Using Shenandoah") || line.contains("-XX:+UseShenandoahGC") {
+        Collector::Shenandoah
+    } else if line.contains("Using Parallel")
+        || line.contains("-XX:+UseParallelGC")
+        || line.contains("-XX:+UseParallelOldGC")
+    {
+        Collector::Parallel
+    } else if line.contains("Using Serial") || line.contains("-XX:+UseSerialGC") {
+        Collector::Serial
+    } else if line.contains("Using Concurrent Mark Sweep")
+        || line.contains("-XX:+UseConcMarkSweepGC")
+    {
+        Collector::Cms
+    } else {
+        Collector::Unknown
+    }
+}
+
+// ローテーション後のファイル等でヘッダが無い場合は、GC行の特徴から推定する
+fn guess_collector(line: &str) -> Collector {
+    if line.contains("Eden regions") || line.contains("[GC pause") {
+        Collector::G1
+    } else if line.contains("Garbage Collection (") || line.contains("Minor Collection") {
+        Collector::Z
+    } else if line.contains("Pause Init Mark") || line.contains("Concurrent reset") {
+        Collector::Shenandoah
+    } else if line.contains("PSYoungGen") {
+        Collector::Parallel
+    } else if line.contains("DefNew") {
+        Collector::Serial
+    } else if line.contains("ParNew") || line.contains("CMS-") {
+        Collector::Cms
+    } else {
+        Collector::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> LogInfo {
+        let lines: Vec<String> = text.lines().take(DETECT_LINES).map(String::from).collect();
+        detect_log_info(&lines)
+    }
+
+    #[test]
+    fn detects_bundled_samples() {
+        let cases = [
+            (
+                include_str!("../../sample/sample_gc.log"),
+                LogFormat::Unified,
+                Collector::G1,
+            ),
+            (
+                include_str!("../../sample/sample_zgc.log"),
+                LogFormat::Unified,
+                Collector::Z,
+            ),
+            (
+                include_str!("../../sample/sample_shenandoah.log"),
+                LogFormat::Unified,
+                Collector::Shenandoah,
+            ),
+            (
+                include_str!("../../sample/sample_parallel.log"),
+                LogFormat::Unified,
+                Collector::Parallel,
+            ),
+            (
+                include_str!("../../sample/sample_gc_jdk8.log"),
+                LogFormat::Jdk8,
+                Collector::G1,
+            ),
+            (
+                include_str!("../../sample/sample_cms_jdk8.log"),
+                LogFormat::Jdk8,
+                Collector::Cms,
+            ),
+            (
+                include_str!("../../sample/sample_parallel_jdk8.log"),
+                LogFormat::Jdk8,
+                Collector::Parallel,
+            ),
+            (
+                include_str!("../../sample/sample_parallel_jdk8_nostamps.log"),
+                LogFormat::Jdk8,
+                Collector::Parallel,
+            ),
+        ];
+        for (text, format, collector) in cases {
+            let info = detect(text);
+            assert_eq!((info.format, info.collector), (format, collector));
+        }
+    }
+
+    #[test]
+    fn reads_jvm_version() {
+        let zgc = detect(include_str!("../../sample/sample_zgc.log"));
+        assert_eq!(zgc.jvm_version.as_deref(), Some("17.0.9+9"));
+        let jdk8 = detect(include_str!("../../sample/sample_cms_jdk8.log"));
+        assert_eq!(jdk8.jvm_version.as_deref(), Some("1.8.0_401-b10"));
+    }
+
+    #[test]
+    fn guesses_collector_without_header() {
+        // ローテーション後のファイルには "Using ..." の行も CommandLine flags も無い
+        let unified = detect(
+            "[2025-04-11T09:50:19.300+0900][2.001s][info][gc,start] GC(7) Concurrent reset\n\
+             [2025-04-11T09:50:19.301+0900][2.001s][info][gc] GC(7) Concurrent reset 600M->600M(4096M) 0.300ms",
+        );
+        assert_eq!(
+            (unified.format, unified.collector),
+            (LogFormat::Unified, Collector::Shenandoah)
+        );
+
+        let date_only = detect(
+            "2025-04-11T09:50:19.433+0900: [GC (Allocation Failure) [DefNew: 69952K->8704K(78656K), 0.0301990 secs] 69952K->20021K(253440K), 0.0302520 secs]",
+        );
+        assert_eq!(
+            (date_only.format, date_only.collector),
+            (LogFormat::Jdk8, Collector::Serial)
+        );
+
+        // JDK 8 でコレクタが分からない場合は Parallel
+        let unknown =
+            detect("1.234: [GC (Allocation Failure)  65536K->10728K(251392K), 0.0083450 secs]");
+        assert_eq!(
+            (unknown.format, unknown.collector),
+            (LogFormat::Jdk8, Collector::Parallel)
+        );
+
+        // -XX:+PrintGCDetails だけでタイムスタンプが無いログ
+        let unstamped = detect(
+            "[GC (Allocation Failure) [PSYoungGen: 139776K->10720K(157184K)] 139776K->10728K(506880K), 0.0083450 secs]\n\
+             [Full GC (Ergonomics) [PSYoungGen: 17392K->0K(157184K)] [ParOldGen: 77085K->79121K(349696K)] 94477K->79121K(506880K), 0.1456780 secs]",
+        );
+        assert_eq!(
+            (unstamped.format, unstamped.collector),
+            (LogFormat::Jdk8, Collector::Parallel)
+        );
+    }
+}
@@ -1,3 +1,11 @@
+pub mod cycle;
 pub mod event;
+pub mod exit;
+pub mod header;
+pub mod log_info;
 
+pub use cycle::*;
 pub use event::*;
+pub use exit::*;
+pub use header::*;
+pub use log_info::*;
@@ -0,0 +1,1164 @@
+use crate::model::{GCEvent, GcCause, GcPhase, GcType};
+use crate::util::heap_block::counts_after_heap_before;
+use crate::util::report::{
+    DATE_TIME_FORMAT, fmt_gc_id, fmt_time_label, print_count_row, print_limited,
+    print_report_header, print_row,
+};
+use crate::util::units::{HeapUnit, MB};
+
+// Max/Avg がこの倍率を超える並列フェーズは、一部のワーカーに処理が偏っている
+const SKEW_THRESHOLD: f64 = 1.5;
+// 平均がこれより短いフェーズは誤差が大きいので偏りの判定から外す
+const SKEW_MIN_AVG_MS: f64 = 1.0;
+// Termination (他のワーカーの終了待ち・work stealing) が Pause に占める割合の閾値
+const TERMINATION_SHARE_THRESHOLD: f64 = 0.1;
+
+// G1 の並列フェーズのワーカー統計から、ワーカー間の負荷の偏りがあった Pause を洗い出す
+pub fn print_worker_imbalance_report(events: &[GCEvent]) {
+    let mut analyzed = 0;
+    let mut max_workers = None;
+    let mut flagged: Vec<(&GCEvent, Option<&GcPhase>, Option<f64>)> = Vec::new();
+    for event in events {
+        let phases = event.worker_phases();
+        if phases.is_empty() {
+            continue;
+        }
+        analyzed += 1;
+        for workers in phases.iter().filter_map(|p| p.workers.as_ref()?.workers) {
+            max_workers = max_workers.max(Some(workers));
+        }
+
+        let (skewed, termination) = worker_imbalance(&phases, event.pause_time_ms);
+        if skewed.is_some() || termination.is_some() {
+            flagged.push((event, skewed, termination));
+        }
+    }
+
+    print_report_header("ワーカー間の負荷の偏り (Worker Imbalance)");
+    if analyzed == 0 {
+        println!(
+            "ワーカーごとの統計がありません (-Xlog:gc+phases=debug / -XX:+PrintGCDetails で出力されます)"
+        );
+        return;
+    }
+    let skewed = flagged.iter().filter(|(_, p, _)| p.is_some()).count();
+    let terminations = flagged.iter().filter(|(_, _, t)| t.is_some()).count();
+    print_count_row("Pauses with worker stats", analyzed);
+    if let Some(workers) = max_workers {
+        print_row("GC workers", format!("{:>4}", workers));
+    }
+    print_count_row(format!("Skewed (Max/Avg >= {:.1})", SKEW_THRESHOLD), skewed);
+    print_count_row(
+        format!(
+            "Termination >= {:.0}% of pause",
+            TERMINATION_SHARE_THRESHOLD * 100.0
+        ),
+        terminations,
+    );
+    if flagged.is_empty() {
+        println!("✅ ワーカー間の負荷の偏りは見つかりませんでした");
+        return;
+    }
+
+    // Pause 時間の長い順
+    flagged.sort_by(|(a, _, _), (b, _, _)| {
+        b.pause_time_ms
+            .unwrap_or(0.0)
+            .total_cmp(&a.pause_time_ms.unwrap_or(0.0))
+    });
+    print_limited(&flagged, |(event, skewed, termination)| {
+        let mut detail = Vec::new();
+        if let Some(phase) = skewed
+            && let Some(stats) = &phase.workers
+        {
+            detail.push(format!(
+                "{} min/avg/max {:.1}/{:.1}/{:.1} ms (x{:.1})",
+                phase.name,
+                stats.min,
+                stats.avg,
+                stats.max,
+                phase_skew(phase)
+            ));
+        }
+        if let Some(share) = termination {
+            detail.push(format!("Termination {:.0}%", share * 100.0));
+        }
+        format!(
+            "{} {:>9.3} ms  {}",
+            event_label(event),
+            event.pause_time_ms.unwrap_or(0.0),
+            detail.join(" / ")
+        )
+    });
+
+    println!(
+        "⚠️ {} / {} 回の Pause でワーカー間の負荷に偏りがあります",
+        flagged.len(),
+        analyzed
+    );
+    if terminations > 0 {
+        println!(
+            "   Termination が長い場合はワーカーが他のワーカーの終了を待っています。-XX:ParallelGCThreads が CPU 数に対して多すぎないか確認してください"
+        );
+    }
+    if skewed > 0 {
+        println!(
+            "   特定のフェーズだけが偏る場合は、長い連結リストや巨大な配列など分割しにくいオブジェクトグラフが原因のことがあります"
+        );
+    }
+}
+
+// 平均からの乖離が最も大きいフェーズと、Termination が Pause に占める割合 (どちらも閾値を超えたものだけ)
+fn worker_imbalance<'a>(
+    phases: &[&'a GcPhase],
+    pause_ms: Option<f64>,
+) -> (Option<&'a GcPhase>, Option<f64>) {
+    let skewed = phases
+        .iter()
+        .copied()
+        .filter(|p| p.duration_ms >= SKEW_MIN_AVG_MS && !is_excluded(p))
+        .filter(|p| phase_skew(p) >= SKEW_THRESHOLD)
+        .max_by(|a, b| phase_skew(a).total_cmp(&phase_skew(b)));
+    let termination = phases
+        .iter()
+        .find(|p| p.name == "Termination")
+        .zip(pause_ms)
+        .filter(|(_, pause)| *pause > 0.0)
+        .map(|(p, pause)| p.duration_ms / pause)
+        .filter(|share| *share >= TERMINATION_SHARE_THRESHOLD);
+    (skewed, termination)
+}
+
+// GC Worker Total はフェーズ全体の合計、Termination は待ち時間そのものなので
+// 偏りの原因となるフェーズの候補から外す
+fn is_excluded(phase: &GcPhase) -> bool {
+    phase.name.starts_with("GC Worker") || phase.name == "Termination"
+}
+
+fn phase_skew(phase: &GcPhase) -> f64 {
+    phase
+        .workers
+        .as_ref()
+        .and_then(|stats| stats.skew())
+        .unwrap_or(0.0)
+}
+
+fn event_time(event: &GCEvent) -> String {
+    fmt_time_label(event.time, event.uptime_secs, DATE_TIME_FORMAT)
+}
+
+// "2025-04-11 09:50:19.433 GC(12)   Young       " (一覧の各行の先頭)
+fn event_label(event: &GCEvent) -> String {
+    format!(
+        "{} {:<8} {:<12}",
+        event_time(event),
+        fmt_gc_id(event.gc_id),
+        format!("{:?}", event.gc_type)
+    )
+}
+
+// sys が user + sys に占める割合の閾値 (カーネル内での待ち: スワップ・ページフォールト・THP など)
+const HIGH_SYS_SHARE: f64 = 0.3;
+// real が user + sys のこの倍率を超えると、GCスレッドが CPU を割り当てられずに待っている
+const REAL_OVER_CPU_RATIO: f64 = 1.5;
+// Times は 10ms 単位なので、短い Pause は判定から外す
+const CPU_MIN_REAL_SECS: f64 = 0.05;
+
+// [gc,cpu] / [Times: ...] の CPU 時間から、ホストの CPU 不足やスワップが疑われる Pause を洗い出す
+pub fn print_cpu_time_report(events: &[GCEvent]) {
+    let timed: Vec<&GCEvent> = events
+        .iter()
+        .filter(|e| e.cpu_real_secs.is_some())
+        .collect();
+
+    print_report_header("GCのCPU時間 (User / Sys / Real)");
+    if timed.is_empty() {
+        println!(
+            "CPU時間がありません ([gc,cpu] / -XX:+PrintGCDetails の [Times: ...] で出力されます)"
+        );
+        return;
+    }
+    let user: f64 = timed.iter().filter_map(|e| e.cpu_user_secs).sum();
+    let sys: f64 = timed.iter().filter_map(|e| e.cpu_sys_secs).sum();
+    let real: f64 = timed.iter().filter_map(|e| e.cpu_real_secs).sum();
+    print_count_row("Pauses with CPU times", timed.len());
+    print_row(
+        "Total user / sys / real",
+        format!("{:.2}s / {:.2}s / {:.2}s", user, sys, real),
+    );
+    if real > 0.0 {
+        print_row(
+            "Parallelism (user+sys)/real",
+            format!("{:>10.2}", (user + sys) / real),
+        );
+    }
+
+    let mut flagged: Vec<(&GCEvent, Vec<String>)> = Vec::new();
+    let (mut high_sys, mut starved) = (0, 0);
+    for event in timed {
+        let (Some(user), Some(sys), Some(real)) =
+            (event.cpu_user_secs, event.cpu_sys_secs, event.cpu_real_secs)
+        else {
+            continue;
+        };
+        let (sys_share, real_ratio) = cpu_starvation(user, sys, real);
+        let mut reasons = Vec::new();
+        if let Some(share) = sys_share {
+            high_sys += 1;
+            reasons.push(format!("sys {:.0}%", share * 100.0));
+        }
+        if let Some(ratio) = real_ratio {
+            starved += 1;
+            reasons.push(format!("real x{:.1} of user+sys", ratio));
+        }
+        if !reasons.is_empty() {
+            flagged.push((event, reasons));
+        }
+    }
+    print_count_row(
+        format!("High sys (>= {:.0}%)", HIGH_SYS_SHARE * 100.0),
+        high_sys,
+    );
+    print_count_row(
+        format!("Real >= {:.1}x user+sys", REAL_OVER_CPU_RATIO),
+        starved,
+    );
+    if flagged.is_empty() {
+        println!("✅ CPU不足やスワップが疑われる Pause は見つかりませんでした");
+        return;
+    }
+
+    flagged.sort_by(|(a, _), (b, _)| {
+        b.cpu_real_secs
+            .unwrap_or(0.0)
+            .total_cmp(&a.cpu_real_secs.unwrap_or(0.0))
+    });
+    print_limited(&flagged, |(event, reasons)| {
+        format!(
+            "{} user={:.2}s sys={:.2}s real={:.2}s  {}",
+            event_label(event),
+            event.cpu_user_secs.unwrap_or(0.0),
+            event.cpu_sys_secs.unwrap_or(0.0),
+            event.cpu_real_secs.unwrap_or(0.0),
+            reasons.join(" / ")
+        )
+    });
+    if high_sys > 0 {
+        println!(
+            "⚠️ sys 時間が長い Pause があります。スワップ、Transparent Huge Pages、メモリ不足によるページの回収を確認してください"
+        );
+    }
+    if starved > 0 {
+        println!(
+            "⚠️ real が user + sys を大きく上回る Pause があります。GCスレッドが CPU を得られていない (ホストの CPU 不足・コンテナの CPU 制限) か、I/O を待っています"
+        );
+    }
+}
+
+// sys が user + sys に占める割合と、real が user + sys の何倍か (どちらも閾値を超えたものだけ)
+fn cpu_starvation(user: f64, sys: f64, real: f64) -> (Option<f64>, Option<f64>) {
+    if real < CPU_MIN_REAL_SECS {
+        return (None, None);
+    }
+    let sys_share =
+        Some(sys / (user + sys)).filter(|share| user + sys > 0.0 && *share >= HIGH_SYS_SHARE);
+    let real_ratio =
+        (real >= (user + sys) * REAL_OVER_CPU_RATIO).then(|| real / (user + sys).max(0.01));
+    (sys_share, real_ratio)
+}
+
+// Heap before/after GC ブロックを、サマリ行・リージョン行や前後の GC と突き合わせる
+pub fn print_heap_consistency_report(events: &[GCEvent]) {
+    let blocks: Vec<&GCEvent> = events
+        .iter()
+        .filter(|e| e.invocations_before.is_some() || e.invocations_after.is_some())
+        .collect();
+
+    print_report_header("Heap before/after GC ブロックの整合性");
+    if blocks.is_empty() {
+        println!("Heap before/after GC ブロックがありません (-Xlog:gc+heap=debug で出力されます)");
+        return;
+    }
+
+    let mut flagged: Vec<(&GCEvent, Vec<String>)> = blocks
+        .iter()
+        .filter(|e| !e.heap_block_issues.is_empty())
+        .map(|e| (*e, e.heap_block_issues.clone()))
+        .collect();
+    let mismatched = flagged.len();
+    // invocations は GC ごとに 1 ずつ増えるので、飛びがあればその間の GC がログに無い
+    // G1 の Remark はブロックを出力せずに invocations を進めるので、間の Remark の数までは許容する
+    let mut gaps = 0;
+    let mut prev: Option<u64> = None;
+    let mut remarks_between = 0;
+    for event in events {
+        let Some(next) = event.invocations_before else {
+            if event.gc_type == GcType::Remark {
+                remarks_between += 1;
+            }
+            continue;
+        };
+        let step = u64::from(!counts_after_heap_before(event));
+        let reason = match prev {
+            Some(prev) if next < prev => Some(format!(
+                "invocations reset {} -> {} (JVM restarted?)",
+                prev, next
+            )),
+            Some(prev) if next > prev + remarks_between + step => Some(format!(
+                "{} GC(s) missing before this one",
+                next - prev - remarks_between - step
+            )),
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            gaps += 1;
+            match flagged.iter_mut().find(|(e, _)| std::ptr::eq(*e, event)) {
+                Some((_, reasons)) => reasons.push(reason),
+                None => flagged.push((event, vec![reason])),
+            }
+        }
+        prev = event.invocations_after;
+        remarks_between = 0;
+    }
+
+    print_count_row("GCs with heap blocks", blocks.len());
+    print_count_row("Mismatch with summary lines", mismatched);
+    print_count_row("Invocation counter gaps", gaps);
+    if flagged.is_empty() {
+        println!("✅ Heap before/after GC ブロックとサマリ行の食い違いは見つかりませんでした");
+        return;
+    }
+
+    flagged.sort_by(|(a, _), (b, _)| {
+        a.uptime_secs
+            .unwrap_or(0.0)
+            .total_cmp(&b.uptime_secs.unwrap_or(0.0))
+    });
+    print_limited(&flagged, |(event, reasons)| {
+        format!("{} {}", event_label(event), reasons.join(" / "))
+    });
+    if mismatched > 0 {
+        println!(
+            "⚠️ ブロックとサマリ行の値が一致しない GC があります。ログ行の欠落や、複数の JVM のログの混在を確認してください"
+        );
+    }
+    if gaps > 0 {
+        println!(
+            "⚠️ invocations が連続していません。ログのローテーションで失われた GC や、JVM の再起動がないか確認してください"
+        );
+    }
+}
+
+// G1 のリージョンサイズの候補 (JDK 18+ は 512MB まで指定できる)
+const MAX_REGION_SIZE_MB: u32 = 32;
+const MAX_REGION_SIZE_MB_JDK18: u32 = 512;
+
+// Humongous オブジェクトが起こしたGC、Humongous リージョンの使用量と回収のされ方、
+// Humongous にならないリージョンサイズを洗い出す
+// Humongous リージョンの値は --units 指定時はその単位、未指定時はリージョン数
+pub fn print_humongous_report(
+    events: &[GCEvent],
+    region_size: Option<f64>,
+    units: Option<HeapUnit>,
+) {
+    print_report_header("Humongous オブジェクト");
+    let pauses: Vec<&GCEvent> = events
+        .iter()
+        .filter(|e| e.has_pause && e.humongous_before.is_some())
+        .collect();
+    if pauses.is_empty() {
+        println!("Humongous リージョンの出力がありません (G1 の [gc,heap] で出力されます)");
+        return;
+    }
+    let label = units.map(|u| u.label()).unwrap_or("regions");
+    let precision = usize::from(units.is_some_and(|u| u != HeapUnit::Bytes));
+    // リージョン数のときはリージョンサイズから MB も併記する
+    let amount = |value: f64| match (units, region_size) {
+        (None, Some(size)) => format!("{:>10.0} {} ({:.0} MB)", value, label, value * size / MB),
+        _ => format!("{:>10.*} {}", precision, value, label),
+    };
+
+    let triggered: Vec<&GCEvent> = events
+        .iter()
+        .filter(|e| e.gc_cause == Some(GcCause::HumongousAllocation))
+        .collect();
+    let triggered_pause: Vec<f64> = triggered.iter().filter_map(|e| e.pause_time_ms).collect();
+    print_count_row("GCs by G1 Humongous Allocation", triggered.len());
+    if !triggered_pause.is_empty() {
+        print_row(
+            "Pause total",
+            format!(
+                "{:>10.3} ms (max {:.3} ms)",
+                triggered_pause.iter().sum::<f64>(),
+                triggered_pause.iter().copied().fold(0.0, f64::max)
+            ),
+        );
+    }
+
+    let peak = pauses
+        .iter()
+        .filter_map(|e| e.humongous_before)
+        .fold(0.0, f64::max);
+    print_row("Peak humongous (before GC)", amount(peak));
+
+    let reclaimed = humongous_reclaimed(&pauses);
+    print_row(
+        "Eagerly reclaimed in Young pauses",
+        format!(
+            "{} ({} 回)",
+            amount(reclaimed.eager),
+            reclaimed.eager_pauses
+        ),
+    );
+    print_row(
+        "Reclaimed between pauses (>=)",
+        amount(reclaimed.between_pauses),
+    );
+    if reclaimed.full > 0.0 {
+        print_row("Reclaimed by Full GC", amount(reclaimed.full));
+    }
+    let reclaim_ms: Vec<f64> = pauses
+        .iter()
+        .filter_map(|e| find_phase(&e.phases, "Humongous Reclaim"))
+        .map(|p| p.duration_ms)
+        .collect();
+    if let Some(max) = reclaim_ms.iter().copied().reduce(f64::max) {
+        print_row(
+            "Humongous Reclaim phase total",
+            format!(
+                "{:>10.3} ms (max {:.3} ms)",
+                reclaim_ms.iter().sum::<f64>(),
+                max
+            ),
+        );
+    }
+
+    let Some(region_size) = region_size else {
+        println!("リージョンサイズが分からないため、G1HeapRegionSize の提案はできません");
+        return;
+    };
+    // リージョンの半分より大きいオブジェクトが Humongous になる
+    print_row(
+        "Humongous threshold",
+        format!(
+            "> {:.0} KB (region {:.0} KB / 2)",
+            region_size / 2.0 / 1024.0,
+            region_size / 1024.0
+        ),
+    );
+    let Some(largest) = pauses
+        .iter()
+        .flat_map(|e| e.humongous_object_sizes.iter().copied())
+        .reduce(f64::max)
+    else {
+        println!(
+            "オブジェクトサイズの出力がないため、G1HeapRegionSize の提案はできません (-Xlog:gc+humongous=debug で出力されます)"
+        );
+        if !triggered.is_empty() || peak > 0.0 {
+            println!(
+                "⚠️ Humongous オブジェクトが割り当てられています。-Xlog:gc+humongous=debug でサイズを確認してください"
+            );
+        }
+        return;
+    };
+    print_row(
+        "Largest humongous object",
+        format!("{:>10.0} KB", largest / 1024.0),
+    );
+    match suggest_region_size(largest, region_size) {
+        RegionSizeAdvice::Fits => {
+            println!("✅ 現在のリージョンサイズで観測したオブジェクトは Humongous になりません");
+        }
+        RegionSizeAdvice::Suggest(mb) => {
+            println!(
+                "⚠️ -XX:G1HeapRegionSize={}m にすると、観測した Humongous オブジェクトは通常のオブジェクトとして割り当てられます",
+                mb
+            );
+        }
+        RegionSizeAdvice::SuggestJdk18(mb) => {
+            println!(
+                "⚠️ -XX:G1HeapRegionSize={}m (JDK 18 以降のみ指定可能) にすると、観測した Humongous オブジェクトは通常のオブジェクトとして割り当てられます",
+                mb
+            );
+        }
+        RegionSizeAdvice::TooLarge => {
+            println!(
+                "⚠️ 最大のリージョンサイズでも Humongous になります。オブジェクト (大きな配列など) の分割を検討してください"
+            );
+        }
+    }
+}
+
+// Humongous リージョンの回収量 (リージョン数、--units 指定時はその単位)
+#[derive(Debug, Default, PartialEq)]
+struct HumongousReclaimed {
+    // Young の Pause で回収された分 (Eager Reclaim) と、回収のあった Pause の数
+    eager: f64,
+    eager_pauses: usize,
+    // Pause の間に減った分 (並行サイクルの Cleanup で回収された分, Pause の間の割り当てと相殺されるので下限)
+    between_pauses: f64,
+    full: f64,
+}
+
+fn humongous_reclaimed(pauses: &[&GCEvent]) -> HumongousReclaimed {
+    let mut reclaimed = HumongousReclaimed::default();
+    for event in pauses {
+        let (Some(before), Some(after)) = (event.humongous_before, event.humongous_after) else {
+            continue;
+        };
+        let amount = (before - after).max(0.0);
+        if event.gc_type == GcType::Full {
+            reclaimed.full += amount;
+        } else if amount > 0.0 {
+            reclaimed.eager += amount;
+            reclaimed.eager_pauses += 1;
+        }
+    }
+    reclaimed.between_pauses = pauses
+        .windows(2)
+        .filter_map(|pair| Some((pair[0].humongous_after? - pair[1].humongous_before?).max(0.0)))
+        .sum();
+    reclaimed
+}
+
+// 観測した最大の Humongous オブジェクトが通常のオブジェクトになるリージョンサイズ
+#[derive(Debug, PartialEq)]
+enum RegionSizeAdvice {
+    Fits,
+    Suggest(u32),
+    // JDK 18 以降でのみ指定できるサイズ
+    SuggestJdk18(u32),
+    // 最大のリージョンサイズでも Humongous になる
+    TooLarge,
+}
+
+// リージョンの半分より大きいオブジェクトが Humongous になる
+fn suggest_region_size(largest: f64, region_size: f64) -> RegionSizeAdvice {
+    match (0..=MAX_REGION_SIZE_MB_JDK18.ilog2())
+        .map(|exp| 2u32.pow(exp))
+        .find(|mb| f64::from(*mb) * MB >= largest * 2.0)
+    {
+        Some(mb) if f64::from(mb) * MB <= region_size => RegionSizeAdvice::Fits,
+        Some(mb) if mb <= MAX_REGION_SIZE_MB => RegionSizeAdvice::Suggest(mb),
+        Some(mb) => RegionSizeAdvice::SuggestJdk18(mb),
+        None => RegionSizeAdvice::TooLarge,
+    }
+}
+
+// 入れ子のフェーズから名前の一致するものを探す
+fn find_phase<'a>(phases: &'a [GcPhase], name: &str) -> Option<&'a GcPhase> {
+    phases.iter().find_map(|phase| {
+        if phase.name == name {
+            Some(phase)
+        } else {
+            find_phase(&phase.children, name)
+        }
+    })
+}
+
+// この割合以上が次の年齢まで生き残る年齢は、Survivor 間でコピーし続けても死なない
+const LONG_LIVED_SURVIVAL: f64 = 0.9;
+
+// テニュアリングしきい値の推移と年齢ごとの生存率から、Old への早期昇格を洗い出す
+// バイト数は --units 指定時はその単位、未指定時は MB
+pub fn print_tenuring_report(
+    events: &[GCEvent],
+    region_size: Option<f64>,
+    units: Option<HeapUnit>,
+) {
+    print_report_header("テニュアリング (年齢分布)");
+    let tenured: Vec<&GCEvent> = events
+        .iter()
+        .filter(|e| e.tenuring_threshold.is_some() || !e.age_table.is_empty())
+        .collect();
+    if tenured.is_empty() {
+        println!(
+            "テニュアリングの出力がありません (-Xlog:gc+age=trace / -XX:+PrintTenuringDistribution で出力されます)"
+        );
+        return;
+    }
+    let label = units.map(|u| u.label()).unwrap_or("MB");
+    let amount = |bytes: f64| match units {
+        Some(_) => bytes,
+        None => bytes / MB,
+    };
+
+    let thresholds: Vec<u32> = tenured
+        .iter()
+        .filter_map(|e| e.tenuring_threshold)
+        .collect();
+    let max_threshold = tenured
+        .iter()
+        .filter_map(|e| e.max_tenuring_threshold)
+        .max();
+    let changes = thresholds.windows(2).filter(|w| w[0] != w[1]).count();
+    let collapsed = tenured.iter().filter(|e| is_premature_promotion(e)).count();
+    print_count_row("GCs with tenuring info", tenured.len());
+    if let Some(max) = max_threshold {
+        print_row("Max tenuring threshold", format!("{:>4}", max));
+    }
+    if let Some(min) = thresholds.iter().min() {
+        print_row(
+            "Threshold avg / min",
+            format!(
+                "{:>6.1} / {}",
+                thresholds.iter().sum::<u32>() as f64 / thresholds.len() as f64,
+                min
+            ),
+        );
+    }
+    print_count_row("Threshold changes", changes);
+    print_count_row("Threshold collapsed to 1", collapsed);
+
+    let mut bytes_by_age: Vec<Vec<f64>> = Vec::new();
+    for event in &tenured {
+        for entry in &event.age_table {
+            let index = entry.age as usize;
+            if bytes_by_age.len() <= index {
+                bytes_by_age.resize(index + 1, Vec::new());
+            }
+            bytes_by_age[index].push(entry.bytes);
+        }
+    }
+    let survival = survival_by_age(&tenured);
+    if !bytes_by_age.is_empty() {
+        println!(
+            "{:<5} {:>14}  Survival to next age",
+            "Age",
+            format!("Avg ({})", label)
+        );
+        for (age, bytes) in bytes_by_age.iter().enumerate() {
+            if bytes.is_empty() {
+                continue;
+            }
+            println!(
+                "{:<5} {:>14.2}  {}",
+                age,
+                amount(bytes.iter().sum::<f64>() / bytes.len() as f64),
+                survival[age]
+                    .map(|rate| format!("{:>5.1}%", rate * 100.0))
+                    .unwrap_or_else(|| "    -".to_string())
+            );
+        }
+    }
+
+    // しきい値が上限より下がった GC (Survivor があふれて若い年齢のまま Old へ昇格した)
+    let lowered: Vec<&GCEvent> = tenured
+        .iter()
+        .copied()
+        .filter(|e| match (e.tenuring_threshold, e.max_tenuring_threshold) {
+            (Some(threshold), Some(max)) => threshold < max,
+            _ => false,
+        })
+        .collect();
+    print_limited(&lowered, |event| {
+        let mut line = format!(
+            "{} {:<8} threshold {} (max {})",
+            event_time(event),
+            fmt_gc_id(event.gc_id),
+            event.tenuring_threshold.unwrap_or(0),
+            event.max_tenuring_threshold.unwrap_or(0)
+        );
+        // G1 は GC 開始時の Survivor から、それ以外は年齢ごとのバイト数の合計からしきい値が決まる
+        let survivors = match (event.survivor_before, units, region_size) {
+            (Some(regions), None, Some(size)) => Some(regions * size),
+            (Some(value), Some(_), _) => Some(value),
+            _ => Some(event.age_table.iter().map(|e| e.bytes).sum::<f64>())
+                .filter(|_| !event.age_table.is_empty()),
+        };
+        if let (Some(survivors), Some(desired)) = (survivors, event.desired_survivor_size) {
+            line += &format!(
+                "  survivors {:.1} {} / desired {:.1} {}",
+                amount(survivors),
+                label,
+                amount(desired),
+                label
+            );
+        }
+        if is_premature_promotion(event) {
+            line += "  ⚠️ premature promotion";
+        }
+        line
+    });
+
+    if collapsed > 0 {
+        println!(
+            "⚠️ Survivor があふれてしきい値が 1 まで下がり、年齢の若いオブジェクトが Old へ早期昇格しています。-XX:SurvivorRatio を下げて Survivor を広げるか、-XX:TargetSurvivorRatio を上げてください"
+        );
+    }
+    let long_lived = long_lived_age(&survival);
+    if let (Some(age), Some(max)) = (long_lived, max_threshold)
+        && (age as u32) < max
+    {
+        println!(
+            "⚠️ 年齢 {} 以降のオブジェクトは {:.0}% 以上が生き残っています。-XX:MaxTenuringThreshold={} にすると Survivor 間のコピーを減らせます",
+            age,
+            LONG_LIVED_SURVIVAL * 100.0,
+            age
+        );
+    }
+    if collapsed == 0 && long_lived.is_none() {
+        println!("✅ 早期昇格は見つかりませんでした");
+    }
+}
+
+// 年齢ごとに、そのバイト数のうち次のGCで年齢 a+1 として残った割合の平均 (しきい値で昇格した分は除く)
+fn survival_by_age(tenured: &[&GCEvent]) -> Vec<Option<f64>> {
+    let ages = tenured
+        .iter()
+        .flat_map(|e| e.age_table.iter().map(|entry| entry.age as usize + 1))
+        .max()
+        .unwrap_or(0);
+    let mut survival_by_age: Vec<Vec<f64>> = vec![Vec::new(); ages];
+    for pair in tenured.windows(2) {
+        let (current, next) = (pair[0], pair[1]);
+        if next.gc_type == GcType::Full || next.age_table.is_empty() {
+            continue;
+        }
+        let kept_below = current
+            .tenuring_threshold
+            .min(next.tenuring_threshold)
+            .unwrap_or(u32::MAX);
+        for entry in &current.age_table {
+            if entry.age >= kept_below || entry.bytes <= 0.0 {
+                continue;
+            }
+            let survived = next
+                .age_table
+                .iter()
+                .find(|e| e.age == entry.age + 1)
+                .map(|e| e.bytes)
+                .unwrap_or(0.0);
+            survival_by_age[entry.age as usize].push((survived / entry.bytes).min(1.0));
+        }
+    }
+    survival_by_age
+        .iter()
+        .map(|rates| (!rates.is_empty()).then(|| rates.iter().sum::<f64>() / rates.len() as f64))
+        .collect()
+}
+
+// しきい値が 1 まで下がると、1回のGCを生き延びただけのオブジェクトが Old へ昇格する
+fn is_premature_promotion(event: &GCEvent) -> bool {
+    event.tenuring_threshold.is_some_and(|t| t <= 1)
+}
+
+// ある年齢以降ほぼ全てが生き残るなら、それ以上 Survivor 間でコピーしても無駄になる
+fn long_lived_age(survival: &[Option<f64>]) -> Option<usize> {
+    (1..survival.len()).find(|&age| {
+        let rates: Vec<f64> = survival[age..].iter().flatten().copied().collect();
+        !rates.is_empty() && rates.iter().all(|rate| *rate >= LONG_LIVED_SURVIVAL)
+    })
+}
+
+// Pause に占める Reference Processing の割合の閾値
+const REF_SHARE_THRESHOLD: f64 = 0.3;
+// 短い Pause は割合が高くても影響が小さいので対象外にする
+const REF_MIN_MS: f64 = 1.0;
+
+// Reference Processing が Pause の大半を占める GC を、割合の大きい順に並べる
+pub fn print_reference_report(events: &[GCEvent]) {
+    print_report_header("参照処理 (Reference Processing)");
+    let measured: Vec<(&GCEvent, f64)> = events
+        .iter()
+        .filter(|e| e.has_pause)
+        .filter_map(|e| reference_time(e).map(|ms| (e, ms)))
+        .collect();
+    if measured.is_empty() {
+        println!(
+            "参照処理の出力がありません (-Xlog:gc+phases=debug,gc+ref=debug / -XX:+PrintReferenceGC で出力されます)"
+        );
+    } else {
+        let total: f64 = measured.iter().map(|(_, ms)| ms).sum();
+        let max = measured.iter().map(|(_, ms)| *ms).fold(0.0, f64::max);
+        print_count_row("Pauses with reference info", measured.len());
+        print_row("Reference processing total", format!("{:>10.3} ms", total));
+        print_row("Reference processing max", format!("{:>10.3} ms", max));
+        for (kind, count, ms) in [
+            (
+                "Soft",
+                sum_refs(events, |e| e.soft_refs),
+                sum_ms(events, |e| e.soft_ref_ms),
+            ),
+            (
+                "Weak",
+                sum_refs(events, |e| e.weak_refs),
+                sum_ms(events, |e| e.weak_ref_ms),
+            ),
+            (
+                "Final",
+                sum_refs(events, |e| e.final_refs),
+                sum_ms(events, |e| e.final_ref_ms),
+            ),
+            (
+                "Phantom",
+                sum_refs(events, |e| e.phantom_refs),
+                sum_ms(events, |e| e.phantom_ref_ms),
+            ),
+        ] {
+            if count.is_none() && ms.is_none() {
+                continue;
+            }
+            print_row(
+                format!("{}Reference discovered / time", kind),
+                format!(
+                    "{:>10} refs / {:>10.3} ms",
+                    count
+                        .map(|c| c.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    ms.unwrap_or(0.0)
+                ),
+            );
+        }
+
+        let mut dominated: Vec<(&GCEvent, f64, f64)> = measured
+            .iter()
+            .filter_map(|(event, ms)| Some((*event, *ms, reference_share(event, *ms)?)))
+            .collect();
+        dominated.sort_by(|a, b| b.2.total_cmp(&a.2));
+        print_limited(&dominated, |(event, ms, share)| {
+            let mut line = format!(
+                "{} {:>9.3} ms  ref {:.3} ms ({:.0}%)",
+                event_label(event),
+                event.pause_time_ms.unwrap_or(0.0),
+                ms,
+                share * 100.0
+            );
+            if let Some(finals) = event.final_refs {
+                line += &format!("  Final {}", finals);
+            }
+            if let Some((kind, kind_ms)) = dominant_reference(event) {
+                line += &format!("  slowest {} {:.3} ms", kind, kind_ms);
+            }
+            line
+        });
+
+        if dominated.is_empty() {
+            println!("✅ 参照処理が Pause の大半を占める GC は見つかりませんでした");
+        } else {
+            println!(
+                "⚠️ {} / {} 回の Pause で参照処理が {:.0}% 以上を占めています",
+                dominated.len(),
+                measured.len(),
+                REF_SHARE_THRESHOLD * 100.0
+            );
+            println!(
+                "   -XX:+ParallelRefProcEnabled が無効なら、有効にすると参照処理を複数のワーカーで並列化できます"
+            );
+            // finalize() を持つクラス (古い JDBC ドライバ, 独自の finalize() 等) のインスタンスごとに FinalReference が作られる
+            if dominated.iter().any(|(event, _, _)| {
+                dominant_reference(event).is_some_and(|(kind, _)| kind == "Final")
+            }) {
+                println!(
+                    "   FinalReference の処理が最も長くなっています。finalize() を実装したクラスを大量に生成するライブラリがないか確認し、Cleaner / try-with-resources への置き換えを検討してください"
+                );
+            }
+        }
+    }
+
+    // [gc,stringtable] の String / Symbol テーブルの掃除
+    let cleaned: Vec<&GCEvent> = events
+        .iter()
+        .filter(|e| e.strings_processed.is_some())
+        .collect();
+    if !cleaned.is_empty() {
+        println!();
+        print_report_header("String / Symbol テーブル");
+        print_count_row("Table cleanups", cleaned.len());
+        print_row(
+            "Strings processed / removed",
+            format!(
+                "{:>10} / {:>10}",
+                sum_refs(events, |e| e.strings_processed).unwrap_or(0),
+                sum_refs(events, |e| e.strings_removed).unwrap_or(0)
+            ),
+        );
+        if let Some(symbols) = sum_refs(events, |e| e.symbols_processed) {
+            print_row(
+                "Symbols processed / removed",
+                format!(
+                    "{:>10} / {:>10}",
+                    symbols,
+                    sum_refs(events, |e| e.symbols_removed).unwrap_or(0)
+                ),
+            );
+        }
+    }
+}
+
+// G1 は "Reference Processing" のフェーズ時間、それ以外は参照型ごとの時間の合計
+fn reference_time(event: &GCEvent) -> Option<f64> {
+    event.ref_processing_ms.or_else(|| {
+        [
+            event.soft_ref_ms,
+            event.weak_ref_ms,
+            event.final_ref_ms,
+            event.phantom_ref_ms,
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(|a, b| a + b)
+    })
+}
+
+// 参照処理が Pause に占める割合 (閾値を超えたものだけ)
+fn reference_share(event: &GCEvent, ms: f64) -> Option<f64> {
+    let pause = event.pause_time_ms.filter(|p| *p > 0.0)?;
+    Some((ms / pause).min(1.0)).filter(|share| ms >= REF_MIN_MS && *share >= REF_SHARE_THRESHOLD)
+}
+
+// 処理時間が最も長い参照型
+fn dominant_reference(event: &GCEvent) -> Option<(&'static str, f64)> {
+    [
+        ("Soft", event.soft_ref_ms),
+        ("Weak", event.weak_ref_ms),
+        ("Final", event.final_ref_ms),
+        ("Phantom", event.phantom_ref_ms),
+    ]
+    .into_iter()
+    .filter_map(|(kind, ms)| ms.map(|ms| (kind, ms)))
+    .filter(|(_, ms)| *ms > 0.0)
+    .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+fn sum_refs(events: &[GCEvent], value: impl Fn(&GCEvent) -> Option<u64>) -> Option<u64> {
+    events.iter().filter_map(value).reduce(|a, b| a + b)
+}
+
+fn sum_ms(events: &[GCEvent], value: impl Fn(&GCEvent) -> Option<f64>) -> Option<f64> {
+    events.iter().filter_map(value).reduce(|a, b| a + b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::WorkerStats;
+    use crate::util::tenuring::apply_tenuring_line;
+    use crate::util::{parse_gc_events, parse_legacy_gc_events};
+
+    const SAMPLE: &str = include_str!("../../sample/sample_gc.log");
+
+    fn sample_events() -> Vec<GCEvent> {
+        parse_gc_events(SAMPLE.lines().map(String::from))
+            .unwrap()
+            .events
+    }
+
+    fn stats(min: f64, avg: f64, max: f64) -> WorkerStats {
+        WorkerStats {
+            min,
+            avg,
+            max,
+            diff: max - min,
+            sum: avg * 4.0,
+            workers: Some(4),
+        }
+    }
+
+    #[test]
+    fn finds_most_skewed_worker_phase() {
+        let events = sample_events();
+        let event = &events[0];
+        // Update RS (ms): Min: 0.0, Avg: 2.3, Max: 7.8 が Ext Root Scanning (x2.7) より偏っている
+        // Termination 1.3ms は Pause 21.425ms の 6% なので対象外
+        let (skewed, termination) = worker_imbalance(&event.worker_phases(), event.pause_time_ms);
+        let skewed = skewed.unwrap();
+        assert_eq!(skewed.name, "Update RS");
+        assert!((phase_skew(skewed) - 7.8 / 2.3).abs() < 1e-9);
+        assert_eq!(termination, None);
+    }
+
+    #[test]
+    fn flags_long_termination_and_ignores_short_phases() {
+        let total = GcPhase::with_workers("GC Worker Total", stats(1.0, 4.0, 12.0));
+        let short = GcPhase::with_workers("Code Root Scanning", stats(0.0, 0.5, 2.0));
+        let even = GcPhase::with_workers("Object Copy", stats(5.0, 6.0, 7.0));
+        let termination = GcPhase::with_workers("Termination", stats(1.0, 2.0, 3.0));
+        let phases = [&total, &short, &even, &termination];
+
+        // 合計のフェーズと平均 1ms 未満のフェーズは偏りの候補にならない
+        let (skewed, share) = worker_imbalance(&phases, Some(10.0));
+        assert!(skewed.is_none());
+        assert_eq!(share, Some(0.2));
+        assert_eq!(worker_imbalance(&phases, Some(40.0)).1, None);
+        assert_eq!(worker_imbalance(&phases, None).1, None);
+    }
+
+    fn cpu_flags(event: &GCEvent) -> (Option<f64>, Option<f64>) {
+        cpu_starvation(
+            event.cpu_user_secs.unwrap(),
+            event.cpu_sys_secs.unwrap(),
+            event.cpu_real_secs.unwrap(),
+        )
+    }
+
+    #[test]
+    fn flags_cpu_starved_pauses() {
+        // "[Times: user=0.15 sys=0.01, real=0.05 secs]" は 3.2 並列で、sys も少ない
+        let line = "2025-04-11T09:50:19.101+0900: 1.801: [GC (Allocation Failure) 2025-04-11T09:50:19.101+0900: 1.801: [ParNew: 157248K->17472K(157248K), 0.0523410 secs] 165160K->78642K(506816K), 0.0524120 secs] [Times: user=0.15 sys=0.01, real=0.05 secs] ";
+        let healthy = &parse_legacy_gc_events(vec![line.to_string()])
+            .unwrap()
+            .events[0];
+        assert_eq!(healthy.parallelism, Some(3.2));
+        assert_eq!(cpu_flags(healthy), (None, None));
+
+        // スワップ中のように sys が多く、real が user + sys の 3 倍かかった Pause
+        let line = line.replace(
+            "user=0.15 sys=0.01, real=0.05",
+            "user=0.05 sys=0.05, real=0.30",
+        );
+        let starved = &parse_legacy_gc_events(vec![line]).unwrap().events[0];
+        assert_eq!(starved.parallelism, Some(0.33));
+        let (sys_share, real_ratio) = cpu_flags(starved);
+        assert!((sys_share.unwrap() - 0.5).abs() < 1e-9);
+        assert!((real_ratio.unwrap() - 3.0).abs() < 1e-9);
+
+        // 10ms 単位の Times では短い Pause の比率は当てにならない
+        assert_eq!(cpu_starvation(0.01, 0.01, 0.04), (None, None));
+
+        // sample_gc.log では "User=0.07s Sys=0.00s Real=0.42s" の GC(19) と Remark の GC(20) だけ
+        let flagged: Vec<(Option<u64>, GcType)> = sample_events()
+            .into_iter()
+            .filter(|e| e.cpu_real_secs.is_some() && cpu_flags(e) != (None, None))
+            .map(|e| (e.gc_id, e.gc_type))
+            .collect();
+        assert_eq!(
+            flagged,
+            [(Some(19), GcType::Young), (Some(20), GcType::Remark)]
+        );
+    }
+
+    #[test]
+    fn sums_humongous_reclaimed_regions() {
+        let events = sample_events();
+        let pauses: Vec<&GCEvent> = events
+            .iter()
+            .filter(|e| e.has_pause && e.humongous_before.is_some())
+            .collect();
+        // "Humongous regions: 1->0" / "3->0" / "4->0" / "518->126" / "516->252" / "513->191" / "446->252"
+        // Pause の間には毎回それ以上割り当てられているので、並行サイクルで回収された分は見えない
+        assert_eq!(
+            humongous_reclaimed(&pauses),
+            HumongousReclaimed {
+                eager: 1180.0,
+                eager_pauses: 7,
+                between_pauses: 0.0,
+                full: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn suggests_region_size_for_largest_object() {
+        // sample_gc.log の GC(19) に -Xlog:gc+humongous=debug の行を足す
+        let humongous = "[2025-04-11T10:00:57.160+0900][639.854s][debug][gc,humongous   ] GC(19) Live humongous region 1720 object size 6291472 start 0x00000007b8000000  with remset 1 code roots 0 is marked 0 reclaim candidate 1 type array 1";
+        let lines = SAMPLE.lines().enumerate().flat_map(|(index, line)| {
+            let inserted = (index == 1008).then_some(humongous);
+            std::iter::once(line).chain(inserted).map(String::from)
+        });
+        let log = parse_gc_events(lines).unwrap();
+        let event = log.events.iter().find(|e| e.gc_id == Some(19)).unwrap();
+        assert_eq!(event.gc_cause, Some(GcCause::HumongousAllocation));
+        assert_eq!(event.humongous_object_sizes, [6291472.0]);
+
+        // 6MB のオブジェクトは 4MB のリージョンでは Humongous、16MB なら半分の 8MB に収まる
+        assert_eq!(
+            suggest_region_size(6291472.0, 4.0 * MB),
+            RegionSizeAdvice::Suggest(16)
+        );
+        assert_eq!(
+            suggest_region_size(1.5 * MB, 4.0 * MB),
+            RegionSizeAdvice::Fits
+        );
+        assert_eq!(
+            suggest_region_size(2.0 * MB, 4.0 * MB),
+            RegionSizeAdvice::Fits
+        );
+        assert_eq!(
+            suggest_region_size(20.0 * MB, 4.0 * MB),
+            RegionSizeAdvice::SuggestJdk18(64)
+        );
+        assert_eq!(
+            suggest_region_size(300.0 * MB, 32.0 * MB),
+            RegionSizeAdvice::TooLarge
+        );
+    }
+
+    // 年齢ごとのバイト数 (MB) の "- age N:" の行を持つ GC
+    fn tenured_event(threshold: u32, ages_mb: &[u64]) -> GCEvent {
+        let mut event = GCEvent::default();
+        apply_tenuring_line(
+            &mut event,
+            &format!(
+                "GC(0) Desired survivor size 29360128 bytes, new threshold {} (max threshold 15)",
+                threshold
+            ),
+        );
+        for (index, mb) in ages_mb.iter().enumerate() {
+            apply_tenuring_line(
+                &mut event,
+                &format!(
+                    "GC(0) - age {:>3}: {:>10} bytes, {:>10} total",
+                    index + 1,
+                    mb << 20,
+                    0
+                ),
+            );
+        }
+        event
+    }
+
+    #[test]
+    fn finds_long_lived_ages() {
+        let events = [
+            tenured_event(15, &[8, 4]),
+            // 年齢 1 の 8MB のうち 2MB、年齢 2 の 4MB は全て残った
+            tenured_event(15, &[6, 2, 4]),
+            // Survivor があふれてしきい値が 1 まで下がったので、年齢 1 以上は昇格して比べられない
+            tenured_event(1, &[10, 1, 1, 4]),
+        ];
+        let tenured: Vec<&GCEvent> = events.iter().collect();
+        let survival = survival_by_age(&tenured);
+        assert_eq!(survival, [None, Some(0.25), Some(1.0), None, None]);
+        assert_eq!(long_lived_age(&survival), Some(2));
+        let premature: Vec<bool> = events.iter().map(is_premature_promotion).collect();
+        assert_eq!(premature, [false, false, true]);
+
+        // 年齢 1 の生存率が低いので、それ以降がすべて生き残っていなければ候補にならない
+        assert_eq!(long_lived_age(&[None, Some(0.25), Some(0.5)]), None);
+        assert_eq!(long_lived_age(&[]), None);
+    }
+
+    #[test]
+    fn finds_pauses_dominated_by_reference_processing() {
+        let events = sample_events();
+        // G1 は "Reference Processing: 0.6ms" のフェーズ時間をそのまま使う
+        assert_eq!(reference_time(&events[0]), Some(0.6));
+        // sample_gc.log の Pause はどれも参照処理が短い
+        assert!(
+            events
+                .iter()
+                .filter_map(|e| Some((e, reference_time(e)?)))
+                .all(|(e, ms)| reference_share(e, ms).is_none())
+        );
+
+        // JDK 8 の -XX:+PrintReferenceGC は参照型ごとの時間の合計
+        let line = "2025-04-11T09:50:19.101+0900: 1.801: [GC (Allocation Failure) 2025-04-11T09:50:19.101+0900: 1.801: [ParNew2025-04-11T09:50:19.105+0900: 1.805: [SoftReference, 0 refs, 0.0000080 secs]2025-04-11T09:50:19.105+0900: 1.805: [WeakReference, 12 refs, 0.0000050 secs]2025-04-11T09:50:19.105+0900: 1.805: [FinalReference, 4817 refs, 0.0301200 secs]2025-04-11T09:50:19.135+0900: 1.835: [PhantomReference, 0 refs, 2 refs, 0.0000060 secs]2025-04-11T09:50:19.135+0900: 1.835: [JNI Weak Reference, 0.0000040 secs]: 157248K->17472K(157248K), 0.0523410 secs] 165160K->78642K(506816K), 0.0524120 secs] [Times: user=0.15 sys=0.01, real=0.05 secs] ";
+        let event = &parse_legacy_gc_events(vec![line.to_string()])
+            .unwrap()
+            .events[0];
+        let ms = reference_time(event).unwrap();
+        assert!((ms - 30.139).abs() < 1e-9);
+        assert!((reference_share(event, ms).unwrap() - ms / 52.412).abs() < 1e-9);
+        assert_eq!(dominant_reference(event), Some(("Final", 30.12)));
+
+        // 割合が高くても 1ms 未満の参照処理は対象外
+        assert_eq!(reference_share(event, 0.9), None);
+    }
+
+    #[test]
+    fn computes_worker_skew() {
+        assert_eq!(stats(0.9, 3.9, 10.4).skew(), Some(10.4 / 3.9));
+        assert_eq!(stats(0.0, 0.0, 0.0).skew(), None);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::util::parser::parse_gc_time;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use regex::Regex;
+use std::sync::LazyLock;
+
+// -Xlog の decorators (time, utctime, uptime, timemillis, uptimemillis, timenanos,
+// uptimenanos, hostname, pid, tid, level, tags) を構造化したもの
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Decorations {
+    pub time: Option<DateTime<FixedOffset>>,
+    pub uptime_secs: Option<f64>,
+    pub pid: Option<u32>,
+    pub tid: Option<u32>,
+    pub level: Option<String>,
+    pub tags: Vec<String>,
+}
+
+static RE_DECORATORS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\[[^\]\[]*\])+").unwrap());
+static RE_DECORATOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]\[]*)\]").unwrap());
+static RE_UPTIME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+(?:\.\d+)?)s$").unwrap());
+static RE_MILLIS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+)ms$").unwrap());
+static RE_NANOS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+)ns$").unwrap());
+// pid / tid は数値のみ、または "12345p" / "12346t" のように種類の接尾辞が付く
+static RE_PROCESS_ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+)([pt])?$").unwrap());
+static RE_TAGS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z][a-z0-9]*(,[a-z0-9]+)*$").unwrap());
+
+// timemillis / timenanos (エポックからの時間) と uptimemillis / uptimenanos の境界
+// (2001年以降のエポックミリ秒は 1e12 を超える)
+const EPOCH_MILLIS_THRESHOLD: u64 = 1_000_000_000_000;
+const EPOCH_NANOS_THRESHOLD: u64 = 1_000_000_000_000_000_000;
+
+const LEVELS: [&str; 5] = ["trace", "debug", "info", "warning", "error"];
+
+// 行頭の decorators を解析し、残りのメッセージ部分と合わせて返す
+// decorators が無い行 (-Xlog:gc*:file:none) は空の Decorations を返す
+pub fn parse_decorations(line: &str) -> (Decorations, &str) {
+    let mut decorations = Decorations::default();
+    let Some(prefix) = RE_DECORATORS.find(line) else {
+        return (decorations, line);
+    };
+    let message = line[prefix.end()..].trim_start();
+
+    let mut numbers: Vec<u32> = Vec::new();
+    for caps in RE_DECORATOR.captures_iter(prefix.as_str()) {
+        let value = caps[1].trim();
+        if let Some(time) = parse_gc_time(value) {
+            decorations.time = decorations.time.or(Some(time));
+        } else if let Some(caps) = RE_UPTIME.captures(value) {
+            decorations.uptime_secs = caps[1].parse().ok();
+        } else if let Some(caps) = RE_MILLIS.captures(value) {
+            let millis: u64 = caps[1].parse().unwrap_or(0);
+            if millis >= EPOCH_MILLIS_THRESHOLD {
+                decorations.time = decorations
+                    .time
+                    .or_else(|| from_epoch_nanos(millis * 1_000_000));
+            } else if decorations.uptime_secs.is_none() {
+                decorations.uptime_secs = Some(millis as f64 / 1000.0);
+            }
+        } else if let Some(caps) = RE_NANOS.captures(value) {
+            let nanos: u64 = caps[1].parse().unwrap_or(0);
+            if nanos >= EPOCH_NANOS_THRESHOLD {
+                decorations.time = decorations.time.or_else(|| from_epoch_nanos(nanos));
+            } else if decorations.uptime_secs.is_none() {
+                decorations.uptime_secs = Some(nanos as f64 / 1_000_000_000.0);
+            }
+        } else if let Some(caps) = RE_PROCESS_ID.captures(value)
+            && let Ok(number) = caps[1].parse::<u32>()
+        {
+            match caps.get(2).map(|m| m.as_str()) {
+                Some("p") => decorations.pid = Some(number),
+                Some("t") => decorations.tid = Some(number),
+                _ => numbers.push(number),
+            }
+        } else if LEVELS.contains(&value) {
+            decorations.level = Some(value.to_string());
+        } else if RE_TAGS.is_match(value) {
+            // hostname も同じ形になり得るが、tags は常に最後に出力される
+            decorations.tags = value.split(',').map(str::to_string).collect();
+        }
+    }
+    // 接尾辞の無い数値は pid, tid の順に出力される
+    let mut numbers = numbers.into_iter();
+    if decorations.pid.is_none() {
+        decorations.pid = numbers.next();
+    }
+    if decorations.tid.is_none() {
+        decorations.tid = numbers.next();
+    }
+
+    (decorations, message)
+}
+
+fn from_epoch_nanos(nanos: u64) -> Option<DateTime<FixedOffset>> {
+    let nanos = i64::try_from(nanos).ok()?;
+    Some(Utc.timestamp_nanos(nanos).fixed_offset())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_decorators() {
+        let line = "[2025-04-11T09:50:19.433+0900][2025-04-11T00:50:19.433+0000][2.127s][1744332619433ms][2127ms][1744332619433000000ns][2127000000ns][app-host][12345][12346][info ][gc,heap      ] GC(0) Eden regions: 75->0(72)";
+        let (decorations, message) = parse_decorations(line);
+        assert_eq!(message, "GC(0) Eden regions: 75->0(72)");
+        assert_eq!(
+            decorations.time,
+            parse_gc_time("2025-04-11T09:50:19.433+0900")
+        );
+        assert_eq!(decorations.uptime_secs, Some(2.127));
+        assert_eq!(decorations.pid, Some(12345));
+        assert_eq!(decorations.tid, Some(12346));
+        assert_eq!(decorations.level.as_deref(), Some("info"));
+        assert_eq!(decorations.tags, vec!["gc", "heap"]);
+    }
+
+    #[test]
+    fn keeps_first_wall_clock_decorator() {
+        // time と utctime は同じ時刻を指すので、先に出力された方のタイムゾーンを使う
+        let (local, _) = parse_decorations(
+            "[2025-04-11T09:50:19.433+0900][2025-04-11T00:50:19.433+0000][2.127s][gc] GC(0) Pause Young",
+        );
+        let time = local.time.unwrap();
+        assert_eq!(time.to_rfc3339(), "2025-04-11T09:50:19.433+09:00");
+
+        let (utc, _) = parse_decorations(
+            "[2025-04-11T00:50:19.433+0000][2025-04-11T09:50:19.433+0900][2.127s][gc] GC(0) Pause Young",
+        );
+        let time = utc.time.unwrap();
+        assert_eq!(time.to_rfc3339(), "2025-04-11T00:50:19.433+00:00");
+    }
+
+    #[test]
+    fn reads_epoch_and_uptime_in_millis_and_nanos() {
+        let (millis, _) = parse_decorations("[1744332619433ms][2127ms][gc] GC(0) Pause Young");
+        assert_eq!(
+            millis.time.map(|t| t.timestamp_millis()),
+            Some(1744332619433)
+        );
+        assert_eq!(millis.uptime_secs, Some(2.127));
+
+        let (nanos, _) = parse_decorations("[2127000000ns][gc] GC(0) Pause Young");
+        assert_eq!(nanos.time, None);
+        assert_eq!(nanos.uptime_secs, Some(2.127));
+    }
+
+    #[test]
+    fn tells_pid_from_tid() {
+        let (suffixed, _) = parse_decorations("[2.127s][12346t][info][gc] GC(0) Pause Young");
+        assert_eq!((suffixed.pid, suffixed.tid), (None, Some(12346)));
+
+        let (both, _) = parse_decorations("[12346t][12345p][gc] GC(0) Pause Young");
+        assert_eq!((both.pid, both.tid), (Some(12345), Some(12346)));
+
+        // 接尾辞が無い場合は pid, tid の順
+        let (plain, _) = parse_decorations("[12345][12346][gc] GC(0) Pause Young");
+        assert_eq!((plain.pid, plain.tid), (Some(12345), Some(12346)));
+    }
+
+    #[test]
+    fn returns_empty_decorations_without_decorators() {
+        let line = "GC(0) Pause Young (Normal) (G1 Evacuation Pause) 307M->17M(6144M) 21.425ms";
+        let (decorations, message) = parse_decorations(line);
+        assert_eq!(decorations, Decorations::default());
+        assert_eq!(message, line);
+    }
+}
@@ -1,39 +1,45 @@
-use crate::model::{GCEvent, GcType};
+use crate::model::{
+    ConcurrentCycle, EvacuationFailure, GCEvent, GcCause, GcPhase, GcType, JvmExitSummary, LogInfo,
+    YoungPhase,
+};
+use crate::util::report::{DATE_TIME_FORMAT, print_count_row, print_report_header, print_row};
+use crate::util::units::{HeapUnit, MB};
+use chrono::{DateTime, FixedOffset};
+use plotters::coord::types::RangedCoordf64;
 use plotters::prelude::*;
 use std::collections::HashMap;
 
 pub fn draw_heap_chart(
     events: &[GCEvent],
     output_path: &str,
+    units: Option<HeapUnit>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new(output_path, (1280, 720)).into_drawing_area();
     root.fill(&WHITE)?;
 
+    let axis = TimeAxis::detect(events);
+    let scale = HeapScale(units);
     let filtered: Vec<&GCEvent> = events
         .iter()
-        .filter(|e| e.has_pause && e.time.is_some())
+        .filter(|e| e.has_pause && axis.x(e).is_some())
         .collect();
     if filtered.is_empty() {
         return Err("No drawable events".into());
     }
 
-    let min_time = filtered.first().unwrap().time.unwrap();
-    let max_time = filtered.last().unwrap().time.unwrap();
+    let min_time = axis.x(filtered.first().unwrap()).unwrap();
+    let max_time = axis.x(filtered.last().unwrap()).unwrap();
 
+    let regions = has_region_data(&filtered);
+    // Remark / Cleanup 等はリージョン数を出力しないので、ヒープの推移からは除く
+    let heap_events: Vec<&GCEvent> = filtered
+        .iter()
+        .copied()
+        .filter(|e| has_heap_data(e, regions))
+        .collect();
     let max_y = filtered
         .iter()
-        .flat_map(|e| {
-            vec![
-                e.eden_before.unwrap_or(0.0),
-                e.eden_after.unwrap_or(0.0),
-                e.survivor_before.unwrap_or(0.0),
-                e.survivor_after.unwrap_or(0.0),
-                e.old_before.unwrap_or(0.0),
-                e.old_after.unwrap_or(0.0),
-                e.humongous_before.unwrap_or(0.0),
-                e.humongous_after.unwrap_or(0.0),
-            ]
-        })
+        .flat_map(|e| heap_values(e, regions, scale))
         .fold(0.0, f64::max);
 
     let mut chart = ChartBuilder::on(&root)
@@ -41,75 +47,26 @@ pub fn draw_heap_chart(
         .margin(20)
         .x_label_area_size(40)
         .y_label_area_size(60)
-        .build_cartesian_2d(min_time..max_time, 0.0..(max_y + 20.0))?;
+        .build_cartesian_2d(min_time..max_time, 0.0..scale.top(max_y))?;
 
     chart
         .configure_mesh()
         .x_labels(10)
-        .x_label_formatter(&|dt| dt.format("%H:%M:%S").to_string())
-        .y_desc("Number of Regions")
-        .x_desc("time")
+        .x_label_formatter(&|x| axis.label(*x))
+        .y_desc(scale.y_desc(regions))
+        .x_desc(axis.desc())
         .label_style(("sans-serif", 20))
         .draw()?;
 
-    macro_rules! draw_dual_series {
-        ($name:expr, $color:expr, $after_accessor:expr, $before_accessor:expr) => {{
-            let after_style = ShapeStyle::from($color).stroke_width(2);
-            let before_style = ShapeStyle::from($color.mix(0.4)).stroke_width(2);
-
-            chart
-                .draw_series(LineSeries::new(
-                    filtered
-                        .iter()
-                        .map(|e| (e.time.unwrap(), $after_accessor(e))),
-                    after_style,
-                ))?
-                .label(format!("{} After", $name))
-                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], after_style));
-
-            chart
-                .draw_series(LineSeries::new(
-                    filtered
-                        .iter()
-                        .map(|e| (e.time.unwrap(), $before_accessor(e))),
-                    before_style,
-                ))?
-                .label(format!("{} Before", $name))
-                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], before_style));
-        }};
-    }
-
-    draw_dual_series!(
-        "Eden",
-        &BLUE,
-        |e: &GCEvent| e.eden_after.unwrap_or(0.0),
-        |e: &GCEvent| e.eden_before.unwrap_or(0.0)
-    );
-    draw_dual_series!(
-        "Survivor",
-        &RED,
-        |e: &GCEvent| e.survivor_after.unwrap_or(0.0),
-        |e: &GCEvent| e.survivor_before.unwrap_or(0.0)
-    );
-    draw_dual_series!(
-        "Old",
-        &GREEN,
-        |e: &GCEvent| e.old_after.unwrap_or(0.0),
-        |e: &GCEvent| e.old_before.unwrap_or(0.0)
-    );
-    draw_dual_series!(
-        "Humongous",
-        &MAGENTA,
-        |e: &GCEvent| e.humongous_after.unwrap_or(0.0),
-        |e: &GCEvent| e.humongous_before.unwrap_or(0.0)
-    );
+    draw_heap_series(&mut chart, &heap_events, axis, regions, scale)?;
+    draw_evacuation_failure_markers(&mut chart, &filtered, axis, Some(scale.top(max_y)), false)?;
 
     chart
         .configure_series_labels()
         .position(SeriesLabelPosition::UpperRight)
         .label_font(("sans-serif", 15))
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     Ok(())
@@ -122,16 +79,17 @@ pub fn draw_pause_chart(
     let root = BitMapBackend::new(output_path, (1280, 720)).into_drawing_area();
     root.fill(&WHITE)?;
 
+    let axis = TimeAxis::detect(events);
     let filtered: Vec<&GCEvent> = events
         .iter()
-        .filter(|e| e.has_pause && e.time.is_some() && e.pause_time_ms.is_some())
+        .filter(|e| e.has_pause && axis.x(e).is_some() && e.pause_time_ms.is_some())
         .collect();
     if filtered.is_empty() {
         return Err("No drawable events".into());
     }
 
-    let min_time = filtered.first().unwrap().time.unwrap();
-    let max_time = filtered.last().unwrap().time.unwrap();
+    let min_time = axis.x(filtered.first().unwrap()).unwrap();
+    let max_time = axis.x(filtered.last().unwrap()).unwrap();
 
     let max_y = filtered
         .iter()
@@ -148,9 +106,9 @@ pub fn draw_pause_chart(
     chart
         .configure_mesh()
         .x_labels(10)
-        .x_label_formatter(&|dt| dt.format("%H:%M:%S").to_string())
+        .x_label_formatter(&|x| axis.label(*x))
         .y_desc("Pause time (ms)")
-        .x_desc("time")
+        .x_desc(axis.desc())
         .label_style(("sans-serif", 20))
         .draw()?;
 
@@ -159,18 +117,20 @@ pub fn draw_pause_chart(
         .draw_series(LineSeries::new(
             filtered
                 .iter()
-                .map(|e| (e.time.unwrap(), e.pause_time_ms.unwrap())),
+                .map(|e| (axis.x(e).unwrap(), e.pause_time_ms.unwrap())),
             pause_style,
         ))?
         .label("Pause Time (ms)")
         .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], pause_style));
+    draw_pause_markers(&mut chart, &filtered, axis)?;
+    draw_evacuation_failure_markers(&mut chart, &filtered, axis, None, true)?;
 
     chart
         .configure_series_labels()
         .position(SeriesLabelPosition::UpperRight)
         .label_font(("sans-serif", 15))
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     Ok(())
@@ -179,35 +139,37 @@ pub fn draw_pause_chart(
 pub fn draw_combined_chart(
     events: &[GCEvent],
     output_path: &str,
+    units: Option<HeapUnit>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new(output_path, (1280, 720)).into_drawing_area();
     root.fill(&WHITE)?;
 
+    let axis = TimeAxis::detect(events);
+    let scale = HeapScale(units);
     let filtered: Vec<&GCEvent> = events
         .iter()
-        .filter(|e| e.has_pause && e.time.is_some())
+        .filter(|e| e.has_pause && axis.x(e).is_some())
         .collect();
     if filtered.is_empty() {
         return Err("No drawable events".into());
     }
 
-    let min_time = filtered.first().unwrap().time.unwrap();
-    let max_time = filtered.last().unwrap().time.unwrap();
+    let min_time = axis.x(filtered.first().unwrap()).unwrap();
+    let max_time = axis.x(filtered.last().unwrap()).unwrap();
 
+    let regions = has_region_data(&filtered);
+    // Remark / Cleanup 等はリージョン数を出力しないので、ヒープの推移からは除く
+    let heap_events: Vec<&GCEvent> = filtered
+        .iter()
+        .copied()
+        .filter(|e| has_heap_data(e, regions))
+        .collect();
     let max_y = filtered
         .iter()
         .flat_map(|e| {
-            vec![
-                e.eden_before.unwrap_or(0.0),
-                e.eden_after.unwrap_or(0.0),
-                e.survivor_before.unwrap_or(0.0),
-                e.survivor_after.unwrap_or(0.0),
-                e.old_before.unwrap_or(0.0),
-                e.old_after.unwrap_or(0.0),
-                e.humongous_before.unwrap_or(0.0),
-                e.humongous_after.unwrap_or(0.0),
-                e.pause_time_ms.unwrap_or(0.0),
-            ]
+            let mut values = heap_values(e, regions, scale);
+            values.push(e.pause_time_ms.unwrap_or(0.0));
+            values
         })
         .fold(0.0, f64::max);
 
@@ -221,63 +183,13 @@ pub fn draw_combined_chart(
     chart
         .configure_mesh()
         .x_labels(10)
-        .x_label_formatter(&|dt| dt.format("%H:%M:%S").to_string())
-        .y_desc("Number of Regions / Pause Time (ms)")
-        .x_desc("時刻")
+        .x_label_formatter(&|x| axis.label(*x))
+        .y_desc(format!("{} / Pause Time (ms)", scale.y_desc(regions)))
+        .x_desc(axis.desc())
         .label_style(("sans-serif", 20))
         .draw()?;
 
-    macro_rules! draw_dual_series {
-        ($name:expr, $color:expr, $after_accessor:expr, $before_accessor:expr) => {{
-            let after_style = ShapeStyle::from($color).stroke_width(2);
-            let before_style = ShapeStyle::from($color.mix(0.4)).stroke_width(2);
-
-            chart
-                .draw_series(LineSeries::new(
-                    filtered
-                        .iter()
-                        .map(|e| (e.time.unwrap(), $after_accessor(e))),
-                    after_style,
-                ))?
-                .label(format!("{} After", $name))
-                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], after_style));
-
-            chart
-                .draw_series(LineSeries::new(
-                    filtered
-                        .iter()
-                        .map(|e| (e.time.unwrap(), $before_accessor(e))),
-                    before_style,
-                ))?
-                .label(format!("{} Before", $name))
-                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], before_style));
-        }};
-    }
-
-    draw_dual_series!(
-        "Eden",
-        &BLUE,
-        |e: &GCEvent| e.eden_after.unwrap_or(0.0),
-        |e: &GCEvent| e.eden_before.unwrap_or(0.0)
-    );
-    draw_dual_series!(
-        "Survivor",
-        &RED,
-        |e: &GCEvent| e.survivor_after.unwrap_or(0.0),
-        |e: &GCEvent| e.survivor_before.unwrap_or(0.0)
-    );
-    draw_dual_series!(
-        "Old",
-        &GREEN,
-        |e: &GCEvent| e.old_after.unwrap_or(0.0),
-        |e: &GCEvent| e.old_before.unwrap_or(0.0)
-    );
-    draw_dual_series!(
-        "Humongous",
-        &MAGENTA,
-        |e: &GCEvent| e.humongous_after.unwrap_or(0.0),
-        |e: &GCEvent| e.humongous_before.unwrap_or(0.0)
-    );
+    draw_heap_series(&mut chart, &heap_events, axis, regions, scale)?;
 
     if filtered.iter().any(|e| e.pause_time_ms.is_some()) {
         let pause_style = ShapeStyle::from(&BLACK).stroke_width(2);
@@ -286,24 +198,453 @@ pub fn draw_combined_chart(
                 filtered
                     .iter()
                     .filter(|e| e.pause_time_ms.is_some())
-                    .map(|e| (e.time.unwrap(), e.pause_time_ms.unwrap())),
+                    .map(|e| (axis.x(e).unwrap(), e.pause_time_ms.unwrap())),
                 pause_style,
             ))?
             .label("Pause Time (ms)")
             .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], pause_style));
+        draw_pause_markers(&mut chart, &filtered, axis)?;
     }
+    draw_evacuation_failure_markers(&mut chart, &filtered, axis, Some(max_y + 20.0), true)?;
 
     chart
         .configure_series_labels()
         .position(SeriesLabelPosition::UpperRight)
         .label_font(("sans-serif", 15))
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    Ok(())
+}
+
+// Metaspace / Class Space の使用量とコミット量の推移 (MB)
+// GC後の使用量が右肩上がりのまま下がらない場合はクラスローダのリークを疑う
+pub fn draw_metaspace_chart(
+    events: &[GCEvent],
+    output_path: &str,
+    units: Option<HeapUnit>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(output_path, (1280, 720)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let axis = TimeAxis::detect(events);
+    // Metaspace はリージョン数には換算しない
+    let scale = HeapScale(units.filter(|unit| *unit != HeapUnit::Regions));
+    let filtered: Vec<&GCEvent> = events
+        .iter()
+        .filter(|e| e.metaspace_used_after.is_some() && axis.x(e).is_some())
+        .collect();
+    if filtered.is_empty() {
+        return Err("No Metaspace data (enable -Xlog:gc+metaspace)".into());
+    }
+
+    let min_time = axis.x(filtered.first().unwrap()).unwrap();
+    let max_time = axis.x(filtered.last().unwrap()).unwrap();
+    let max_y = filtered
+        .iter()
+        .flat_map(|e| {
+            [
+                e.metaspace_used_before,
+                e.metaspace_used_after,
+                e.metaspace_committed,
+            ]
+        })
+        .map(|value| scale.bytes(value))
+        .fold(0.0, f64::max);
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Metaspace usage (Before/After)", ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(min_time..max_time, 0.0..(max_y * 1.1 + 1.0))?;
+
+    chart
+        .configure_mesh()
+        .x_labels(10)
+        .x_label_formatter(&|x| axis.label(*x))
+        .y_desc(format!("Metaspace size ({})", scale.unit_label()))
+        .x_desc(axis.desc())
+        .label_style(("sans-serif", 20))
+        .draw()?;
+
+    macro_rules! draw_line {
+        ($name:expr, $style:expr, $accessor:expr) => {{
+            let style = $style;
+            let points: Vec<(f64, f64)> = filtered
+                .iter()
+                .filter_map(|e| Some((axis.x(e)?, scale.bytes(Some($accessor(e)?)))))
+                .collect();
+            if !points.is_empty() {
+                chart
+                    .draw_series(LineSeries::new(points, style))?
+                    .label($name)
+                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], style));
+            }
+        }};
+    }
+
+    draw_line!(
+        "Metaspace Used After",
+        ShapeStyle::from(&BLUE).stroke_width(2),
+        |e: &GCEvent| e.metaspace_used_after
+    );
+    draw_line!(
+        "Metaspace Used Before",
+        ShapeStyle::from(&BLUE.mix(0.4)).stroke_width(2),
+        |e: &GCEvent| e.metaspace_used_before
+    );
+    draw_line!(
+        "Metaspace Committed",
+        ShapeStyle::from(&BLACK).stroke_width(1),
+        |e: &GCEvent| e.metaspace_committed
+    );
+    draw_line!(
+        "Class Space Used After",
+        ShapeStyle::from(&GREEN).stroke_width(2),
+        |e: &GCEvent| e.class_space_used_after
+    );
+    draw_line!(
+        "Class Space Used Before",
+        ShapeStyle::from(&GREEN.mix(0.4)).stroke_width(2),
+        |e: &GCEvent| e.class_space_used_before
+    );
+
+    // Metaspace の閾値超過で起きたGC
+    let threshold: Vec<(f64, f64)> = filtered
+        .iter()
+        .filter(|e| {
+            matches!(
+                e.gc_cause,
+                Some(GcCause::MetadataGcThreshold | GcCause::MetadataGcClearSoftReferences)
+            )
+        })
+        .filter_map(|e| {
+            let used = e.metaspace_used_before.or(e.metaspace_used_after);
+            Some((axis.x(e)?, scale.bytes(used)))
+        })
+        .collect();
+    if !threshold.is_empty() {
+        let style = RED.filled();
+        chart
+            .draw_series(
+                threshold
+                    .into_iter()
+                    .map(|p| TriangleMarker::new(p, 6, style)),
+            )?
+            .label("Metadata GC Threshold")
+            .legend(move |(x, y)| TriangleMarker::new((x + 10, y), 6, style));
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .label_font(("sans-serif", 15))
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     Ok(())
 }
 
+// Pause の種類 (Young / Mixed / Remark / Cleanup / Full 等) ごとに色分けした点を重ねる
+const PAUSE_KINDS: [GcType; 15] = [
+    GcType::Young,
+    GcType::PrepareMixed,
+    GcType::Mixed,
+    GcType::Remark,
+    GcType::Cleanup,
+    GcType::Full,
+    GcType::Minor,
+    GcType::Major,
+    GcType::Concurrent,
+    GcType::Degenerated,
+    GcType::InitialMark,
+    GcType::InitMark,
+    GcType::FinalMark,
+    GcType::InitUpdateRefs,
+    GcType::FinalUpdateRefs,
+];
+
+// GC 前後のヒープ使用量 (リージョン数なら Eden / Survivor / Old / Humongous、
+// それ以外はヒープ全体と、出力があれば Young / Old 世代) を同じ色の濃淡で2本ずつ描く
+fn draw_heap_series<DB: DrawingBackend>(
+    chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    heap_events: &[&GCEvent],
+    axis: TimeAxis,
+    regions: bool,
+    scale: HeapScale,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    type Value = fn(&GCEvent) -> Option<f64>;
+    let mut series: Vec<(&str, RGBColor, Value, Value)> = if regions {
+        vec![
+            ("Eden", BLUE, |e| e.eden_after, |e| e.eden_before),
+            ("Survivor", RED, |e| e.survivor_after, |e| e.survivor_before),
+            ("Old", GREEN, |e| e.old_after, |e| e.old_before),
+            (
+                "Humongous",
+                MAGENTA,
+                |e| e.humongous_after,
+                |e| e.humongous_before,
+            ),
+        ]
+    } else {
+        vec![(
+            "Heap Used",
+            BLUE,
+            |e| e.heap_used_after,
+            |e| e.heap_used_before,
+        )]
+    };
+    if !regions && has_generation_data(heap_events) {
+        series.push((
+            "Young Gen",
+            RED,
+            |e| e.young_gen_after,
+            |e| e.young_gen_before,
+        ));
+        series.push(("Old Gen", GREEN, |e| e.old_gen_after, |e| e.old_gen_before));
+    }
+
+    // リージョン数はそのまま、バイト数は --units の単位 (未指定時は MB) で描く
+    let y = |value: Option<f64>| {
+        if regions {
+            value.unwrap_or(0.0)
+        } else {
+            scale.bytes(value)
+        }
+    };
+    for (name, color, after, before) in series {
+        for (suffix, value, style) in [
+            ("After", after, ShapeStyle::from(&color).stroke_width(2)),
+            (
+                "Before",
+                before,
+                ShapeStyle::from(&color.mix(0.4)).stroke_width(2),
+            ),
+        ] {
+            chart
+                .draw_series(LineSeries::new(
+                    heap_events
+                        .iter()
+                        .map(|e| (axis.x(e).unwrap(), y(value(e)))),
+                    style,
+                ))?
+                .label(format!("{} {}", name, suffix))
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], style));
+        }
+    }
+    Ok(())
+}
+
+fn draw_pause_markers<DB: DrawingBackend>(
+    chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    events: &[&GCEvent],
+    axis: TimeAxis,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    for (index, kind) in PAUSE_KINDS.iter().enumerate() {
+        let points: Vec<(f64, f64)> = events
+            .iter()
+            .filter(|e| e.gc_type == *kind)
+            .filter_map(|e| Some((axis.x(e)?, e.pause_time_ms?)))
+            .collect();
+        if points.is_empty() {
+            continue;
+        }
+        let style = Palette99::pick(index).filled();
+        chart
+            .draw_series(points.into_iter().map(|p| Circle::new(p, 4, style)))?
+            .label(format!("{:?}", kind))
+            .legend(move |(x, y)| Circle::new((x + 10, y), 4, style));
+    }
+    Ok(())
+}
+
+// 退避失敗 (To-space exhausted / Evacuation Failure) の Pause を強調する
+// top を指定するとヒープの推移にも縦線を引き、pauses のときは Pause 時間の点に×印を重ねる
+fn draw_evacuation_failure_markers<DB: DrawingBackend>(
+    chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    events: &[&GCEvent],
+    axis: TimeAxis,
+    top: Option<f64>,
+    pauses: bool,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let failures: Vec<&GCEvent> = events
+        .iter()
+        .copied()
+        .filter(|e| e.evacuation_failure.is_some())
+        .collect();
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let line_style = ShapeStyle::from(&RED.mix(0.6)).stroke_width(2);
+    let cross_style = ShapeStyle::from(&RED).stroke_width(3);
+    if let Some(top) = top {
+        chart
+            .draw_series(failures.iter().filter_map(|e| {
+                let x = axis.x(e)?;
+                Some(PathElement::new(vec![(x, 0.0), (x, top)], line_style))
+            }))?
+            .label("Evacuation Failure")
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x + 10, y - 8), (x + 10, y + 8)], line_style)
+            });
+    }
+    if pauses {
+        let series = chart.draw_series(
+            failures
+                .iter()
+                .filter_map(|e| Some((axis.x(e)?, e.pause_time_ms?)))
+                .map(|p| Cross::new(p, 8, cross_style)),
+        )?;
+        if top.is_none() {
+            series
+                .label("Evacuation Failure")
+                .legend(move |(x, y)| Cross::new((x + 10, y), 6, cross_style));
+        }
+    }
+    Ok(())
+}
+
+// X軸: 壁時計時刻があればそれを使い、無ければJVM起動からの経過時間で描画する
+// decorators が無いログ (-Xlog:gc*:file=gc.log:none) は時刻が取れないので GC ID を並べる
+#[derive(Clone, Copy)]
+enum TimeAxis {
+    Wall(FixedOffset),
+    Uptime,
+    GcId,
+}
+
+impl TimeAxis {
+    fn detect(events: &[GCEvent]) -> TimeAxis {
+        if let Some(time) = events.iter().find_map(|e| e.time) {
+            TimeAxis::Wall(*time.offset())
+        } else if events.iter().any(|e| e.uptime_secs.is_some()) {
+            TimeAxis::Uptime
+        } else {
+            TimeAxis::GcId
+        }
+    }
+
+    // 壁時計時刻はエポック秒に変換して f64 の座標に揃える
+    fn x(&self, e: &GCEvent) -> Option<f64> {
+        match self {
+            TimeAxis::Wall(_) => e.time.map(|t| t.timestamp_millis() as f64 / 1000.0),
+            TimeAxis::Uptime => e.uptime_secs,
+            TimeAxis::GcId => e.gc_id.map(|id| id as f64),
+        }
+    }
+
+    fn label(&self, x: f64) -> String {
+        match self {
+            TimeAxis::Wall(offset) => DateTime::from_timestamp_millis((x * 1000.0) as i64)
+                .map(|t| t.with_timezone(offset).format("%H:%M:%S").to_string())
+                .unwrap_or_default(),
+            TimeAxis::Uptime => format!("{:.0}s", x),
+            TimeAxis::GcId => format!("{:.0}", x),
+        }
+    }
+
+    fn desc(&self) -> &'static str {
+        match self {
+            TimeAxis::Wall(_) => "time",
+            TimeAxis::Uptime => "uptime",
+            TimeAxis::GcId => "GC id",
+        }
+    }
+}
+
+fn to_mb(bytes: Option<f64>) -> f64 {
+    bytes.unwrap_or(0.0) / MB
+}
+
+// 描画するヒープ量の単位
+// --units 指定時はイベントの値が換算済みなのでそのまま描画し、未指定時はバイト数をMBにする
+#[derive(Clone, Copy)]
+struct HeapScale(Option<HeapUnit>);
+
+impl HeapScale {
+    fn bytes(&self, value: Option<f64>) -> f64 {
+        match self.0 {
+            Some(_) => value.unwrap_or(0.0),
+            None => to_mb(value),
+        }
+    }
+
+    // Y軸の上限 (GB 等の小さい値でも余白が大きくなりすぎないようにする)
+    fn top(&self, max_y: f64) -> f64 {
+        match self.0 {
+            Some(HeapUnit::Gb | HeapUnit::Bytes) => max_y * 1.05,
+            _ => max_y + 20.0,
+        }
+    }
+
+    fn unit_label(&self) -> &'static str {
+        self.0.map_or("MB", |unit| unit.label())
+    }
+
+    fn y_desc(&self, regions: bool) -> String {
+        match self.0 {
+            None if regions => "Number of Regions".to_string(),
+            Some(HeapUnit::Regions) => "Number of Regions".to_string(),
+            _ => format!("Heap size ({})", self.unit_label()),
+        }
+    }
+}
+
+// リージョン数を出力しないコレクタ (ZGC, Parallel, Serial等) はヒープ使用量をMB単位で描画する
+fn has_region_data(events: &[&GCEvent]) -> bool {
+    events.iter().any(|e| e.eden_before.is_some())
+}
+
+fn has_heap_data(e: &GCEvent, regions: bool) -> bool {
+    if regions {
+        e.eden_before.is_some()
+    } else {
+        e.heap_used_before.is_some() || e.young_gen_before.is_some()
+    }
+}
+
+fn has_generation_data(events: &[&GCEvent]) -> bool {
+    events
+        .iter()
+        .any(|e| e.young_gen_before.is_some() || e.old_gen_before.is_some())
+}
+
+fn heap_values(e: &GCEvent, regions: bool, scale: HeapScale) -> Vec<f64> {
+    if regions {
+        vec![
+            e.eden_before.unwrap_or(0.0),
+            e.eden_after.unwrap_or(0.0),
+            e.survivor_before.unwrap_or(0.0),
+            e.survivor_after.unwrap_or(0.0),
+            e.old_before.unwrap_or(0.0),
+            e.old_after.unwrap_or(0.0),
+            e.humongous_before.unwrap_or(0.0),
+            e.humongous_after.unwrap_or(0.0),
+        ]
+    } else {
+        vec![
+            scale.bytes(e.heap_used_before),
+            scale.bytes(e.heap_used_after),
+            scale.bytes(e.young_gen_before),
+            scale.bytes(e.young_gen_after),
+            scale.bytes(e.old_gen_before),
+            scale.bytes(e.old_gen_after),
+        ]
+    }
+}
+
 pub fn count_gc_types(events: &[GCEvent]) -> HashMap<GcType, usize> {
     let mut counts = HashMap::new();
     for event in events {
@@ -312,10 +653,568 @@ pub fn count_gc_types(events: &[GCEvent]) -> HashMap<GcType, usize> {
     counts
 }
 
+pub fn count_gc_causes(events: &[GCEvent]) -> HashMap<GcCause, usize> {
+    let mut counts = HashMap::new();
+    for cause in events.iter().filter_map(|e| e.gc_cause.clone()) {
+        *counts.entry(cause).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub fn count_young_phases(events: &[GCEvent]) -> HashMap<YoungPhase, usize> {
+    let mut counts = HashMap::new();
+    for phase in events.iter().filter_map(|e| e.young_phase.clone()) {
+        *counts.entry(phase).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub fn print_log_info(info: &LogInfo, units: Option<HeapUnit>) {
+    print_report_header("検出したログ形式");
+    print_row("Format", format!("{:?}", info.format));
+    print_row("Collector", format!("{:?}", info.collector));
+    if let Some(version) = &info.jvm_version {
+        print_row("JVM", version);
+    }
+
+    let header = &info.header;
+    let unit = units.unwrap_or(HeapUnit::Mb);
+    if let Some(size) = header.region_size {
+        print_row("Region size", format!("{:.0} KB", size / 1024.0));
+    }
+    let sizes = [
+        ("Min heap", header.min_heap),
+        ("Initial heap", header.initial_heap),
+        ("Max heap", header.max_heap),
+    ];
+    for (name, size) in sizes {
+        if let Some(size) = size {
+            print_row(name, unit.format_bytes(size, header.region_size));
+        }
+    }
+    if let Some(mode) = &header.compressed_oops {
+        print_row("Compressed Oops", mode);
+    }
+    if let Some(workers) = header.parallel_workers {
+        print_row("GC workers", workers);
+    }
+    if let Some(workers) = header.concurrent_workers {
+        print_row("Conc workers", workers);
+    }
+}
+
+// GC前後のヒープ使用量 (--units 指定時はその単位、未指定時はMB)
+pub fn print_heap_summary(events: &[GCEvent], units: Option<HeapUnit>) {
+    let scale = HeapScale(units);
+    let before: Vec<f64> = events
+        .iter()
+        .filter(|e| e.heap_used_before.is_some())
+        .map(|e| scale.bytes(e.heap_used_before))
+        .collect();
+    let after: Vec<f64> = events
+        .iter()
+        .filter(|e| e.heap_used_after.is_some())
+        .map(|e| scale.bytes(e.heap_used_after))
+        .collect();
+    if after.is_empty() {
+        return;
+    }
+    let label = scale.unit_label();
+    print_report_header(&format!("ヒープ使用量 ({})", label));
+    print_row(
+        "Peak before GC",
+        format!(
+            "{:>12.1} {}",
+            before.iter().copied().fold(0.0, f64::max),
+            label
+        ),
+    );
+    print_row(
+        "Avg after GC",
+        format!(
+            "{:>12.1} {}",
+            after.iter().sum::<f64>() / after.len() as f64,
+            label
+        ),
+    );
+    print_row(
+        "Max after GC",
+        format!(
+            "{:>12.1} {}",
+            after.iter().copied().fold(0.0, f64::max),
+            label
+        ),
+    );
+    if let Some(committed) = events
+        .iter()
+        .filter_map(|e| e.heap_committed)
+        .reduce(f64::max)
+    {
+        print_row(
+            "Max committed",
+            format!("{:>12.1} {}", scale.bytes(Some(committed)), label),
+        );
+    }
+}
+
+// JVM 終了時のヒープの状態 (最後のGCから終了までに増えた分も分かる)
+// units は GCEvent と同じ単位で表示するためのもので、exit 自体は bytes のまま
+pub fn print_exit_summary(
+    exit: &JvmExitSummary,
+    events: &[GCEvent],
+    units: Option<HeapUnit>,
+    region_size: Option<f64>,
+) {
+    let unit = units.unwrap_or(HeapUnit::Mb);
+    // Metaspace はヒープ外なのでリージョン数では表示しない
+    let metaspace_unit = if unit == HeapUnit::Regions {
+        HeapUnit::Mb
+    } else {
+        unit
+    };
+    print_report_header("JVM終了時のヒープ");
+    match (exit.time, exit.uptime_secs) {
+        (Some(time), Some(uptime)) => print_row(
+            "Exit at",
+            format!("{} ({:.3}s)", time.format(DATE_TIME_FORMAT), uptime),
+        ),
+        (None, Some(uptime)) => print_row("Exit at", format!("{:.3}s", uptime)),
+        _ => {}
+    }
+    let last_gc = events
+        .iter()
+        .filter(|e| e.heap_used_after.is_some())
+        .max_by(|a, b| {
+            a.uptime_secs
+                .unwrap_or(0.0)
+                .total_cmp(&b.uptime_secs.unwrap_or(0.0))
+        });
+    if let (Some(exit_uptime), Some(last_uptime)) =
+        (exit.uptime_secs, last_gc.and_then(|e| e.uptime_secs))
+    {
+        print_row(
+            "Since last GC",
+            format!("{:.3}s", exit_uptime - last_uptime),
+        );
+    }
+    if let Some(used) = exit.heap_used {
+        match exit.heap_total.filter(|total| *total > 0.0) {
+            Some(total) => print_row(
+                "Heap used",
+                format!(
+                    "{} / {} ({:.1}%)",
+                    unit.format_bytes(used, region_size),
+                    unit.format_bytes(total, region_size),
+                    used / total * 100.0
+                ),
+            ),
+            None => print_row("Heap used", unit.format_bytes(used, region_size)),
+        }
+    }
+    // GCEvent は --units で換算済みなので、そのままの値を表示する
+    if let Some(after) = last_gc.and_then(|e| e.heap_used_after) {
+        let value = if units.is_some() { after } else { after / MB };
+        let precision = usize::from(unit != HeapUnit::Bytes);
+        print_row(
+            "Last GC after",
+            format!("{:.*} {}", precision, value, unit.label()),
+        );
+    }
+    if let (Some(young), Some(survivors)) = (exit.young_regions, exit.survivor_regions) {
+        print_row(
+            "Young regions",
+            format!("{:.0} (survivors {:.0})", young, survivors),
+        );
+    }
+    let spaces = [
+        (
+            "Metaspace",
+            exit.metaspace_used,
+            exit.metaspace_committed,
+            exit.metaspace_reserved,
+        ),
+        (
+            "Class space",
+            exit.class_space_used,
+            exit.class_space_committed,
+            exit.class_space_reserved,
+        ),
+    ];
+    for (name, used, committed, reserved) in spaces {
+        let Some(used) = used else {
+            continue;
+        };
+        let mut line = format!("used {}", metaspace_unit.format_bytes(used, region_size));
+        if let Some(committed) = committed {
+            line += &format!(
+                ", committed {}",
+                metaspace_unit.format_bytes(committed, region_size)
+            );
+        }
+        if let Some(reserved) = reserved {
+            line += &format!(
+                ", reserved {}",
+                metaspace_unit.format_bytes(reserved, region_size)
+            );
+        }
+        print_row(name, line);
+    }
+}
+
 pub fn print_gc_type_summary(counts: &HashMap<GcType, usize>) {
-    println!("GCイベント種別別の発生回数");
-    println!("────────────────────────");
-    for (gc_type, count) in counts {
-        println!("{:<15} : {:>4} 回", format!("{:?}", gc_type), count);
+    print_report_header("GCイベント種別別の発生回数");
+    for (gc_type, count) in sorted_counts(counts) {
+        print_count_row(gc_type, count);
+    }
+    // Degenerated GC は並行GCが追いついていない兆候なので別途警告する
+    if let Some(count) = counts.get(&GcType::Degenerated) {
+        println!("⚠️ Degenerated GC が {} 回発生しています", count);
+    }
+}
+
+pub fn print_gc_cause_summary(counts: &HashMap<GcCause, usize>) {
+    print_report_header("GC原因別の発生回数");
+    for (cause, count) in sorted_counts(counts) {
+        print_count_row(cause, count);
+    }
+}
+
+// HashMap の順序は実行ごとに変わるので、回数の多い順 (同数なら名前順) に並べる
+fn sorted_counts<K: std::fmt::Debug>(counts: &HashMap<K, usize>) -> Vec<(String, usize)> {
+    let mut sorted: Vec<(String, usize)> = counts
+        .iter()
+        .map(|(key, count)| (format!("{:?}", key), *count))
+        .collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    sorted
+}
+
+pub fn print_young_phase_summary(counts: &HashMap<YoungPhase, usize>) {
+    if counts.is_empty() {
+        return;
+    }
+    print_report_header("G1 Pause Young の種類別の発生回数");
+    for phase in [
+        YoungPhase::Normal,
+        YoungPhase::ConcurrentStart,
+        YoungPhase::PrepareMixed,
+        YoungPhase::Mixed,
+    ] {
+        if let Some(count) = counts.get(&phase) {
+            print_count_row(format!("{:?}", phase), count);
+        }
+    }
+}
+
+// 退避失敗は Full GC の前兆なので、種類ごとの回数と直後に Full GC が起きた回数を表示する
+pub fn print_evacuation_failure_summary(events: &[GCEvent]) {
+    let failures = count_evacuation_failures(events);
+    let total: usize = failures.counts.values().sum();
+    print_report_header("退避失敗 (To-space exhausted / Evacuation Failure)");
+    print_count_row("Evacuation failures", total);
+    if total == 0 {
+        return;
+    }
+    for failure in [
+        EvacuationFailure::ToSpaceExhausted,
+        EvacuationFailure::Failure,
+        EvacuationFailure::Allocation,
+        EvacuationFailure::Pinned,
+        EvacuationFailure::AllocationPinned,
+    ] {
+        if let Some(count) = failures.counts.get(&failure) {
+            print_count_row(format!("  {:?}", failure), count);
+        }
+    }
+    print_count_row("Followed by Full GC", failures.followed_by_full);
+    println!(
+        "⚠️ 退避失敗が {} 回発生しています。-XX:G1ReservePercent や -Xmx の引き上げ、IHOP (-XX:InitiatingHeapOccupancyPercent) の引き下げを検討してください",
+        total
+    );
+}
+
+// 退避失敗の種類ごとの回数と、直後の Pause が Full GC だった回数
+#[derive(Debug, Default, PartialEq)]
+struct EvacuationFailureCounts {
+    counts: HashMap<EvacuationFailure, usize>,
+    followed_by_full: usize,
+}
+
+fn count_evacuation_failures(events: &[GCEvent]) -> EvacuationFailureCounts {
+    let pauses: Vec<&GCEvent> = events.iter().filter(|e| e.has_pause).collect();
+    let mut failures = EvacuationFailureCounts::default();
+    for (index, event) in pauses.iter().enumerate() {
+        let Some(failure) = event.evacuation_failure else {
+            continue;
+        };
+        *failures.counts.entry(failure).or_insert(0) += 1;
+        if pauses
+            .get(index + 1)
+            .is_some_and(|next| next.gc_type == GcType::Full)
+        {
+            failures.followed_by_full += 1;
+        }
+    }
+    failures
+}
+
+pub fn print_concurrent_cycle_summary(cycles: &[ConcurrentCycle]) {
+    if cycles.is_empty() {
+        return;
+    }
+    let durations: Vec<f64> = cycles.iter().filter_map(|c| c.duration_ms).collect();
+    let aborted = cycles.iter().filter(|c| c.aborted).count();
+    print_report_header("並行サイクル (Concurrent Cycle)");
+    print_count_row("Cycles", cycles.len());
+    if !durations.is_empty() {
+        let avg = durations.iter().sum::<f64>() / durations.len() as f64;
+        let max = durations.iter().copied().fold(0.0, f64::max);
+        print_row(
+            "Duration avg",
+            format!("{:>10.3} ms (max {:.3} ms)", avg, max),
+        );
+    }
+
+    // フェーズごとの平均・最大 (ログに出現した順)
+    let mut phases: Vec<(&str, Vec<f64>)> = Vec::new();
+    for phase in cycles.iter().flat_map(|c| &c.phases) {
+        match phases.iter_mut().find(|(name, _)| *name == phase.name) {
+            Some((_, values)) => values.push(phase.duration_ms),
+            None => phases.push((&phase.name, vec![phase.duration_ms])),
+        }
+    }
+    for (name, values) in phases {
+        let avg = values.iter().sum::<f64>() / values.len() as f64;
+        let max = values.iter().copied().fold(0.0, f64::max);
+        print_row(name, format!("{:>10.3} ms (max {:.3} ms)", avg, max));
+    }
+
+    if aborted > 0 {
+        println!(
+            "⚠️ 並行サイクルが {} 回中断されています (Concurrent Mark Abort / concurrent mode failure)",
+            aborted
+        );
+    }
+}
+
+// Pause のフェーズ内訳を GC種別ごとに集計し、入れ子のまま平均・最大と Pause 時間に占める割合を表示する
+// ZGC / Shenandoah のサイクル (世代別ZGCの Minor / Major を含む) は並行フェーズを含むので対象外
+pub fn print_pause_phase_summary(events: &[GCEvent]) {
+    let mut groups: Vec<(&GcType, Vec<f64>, Vec<PhaseStats>)> = Vec::new();
+    for event in events.iter().filter(|e| {
+        e.has_pause
+            && !matches!(
+                e.gc_type,
+                GcType::Concurrent | GcType::Minor | GcType::Major
+            )
+            && !e.phases.is_empty()
+    }) {
+        let index = match groups.iter().position(|(t, _, _)| *t == &event.gc_type) {
+            Some(index) => index,
+            None => {
+                groups.push((&event.gc_type, Vec::new(), Vec::new()));
+                groups.len() - 1
+            }
+        };
+        let (_, pauses, phases) = &mut groups[index];
+        pauses.extend(event.pause_time_ms);
+        collect_phases(&event.phases, "", 0, phases);
+    }
+    if groups.is_empty() {
+        return;
+    }
+
+    print_report_header("Pause フェーズ内訳");
+    for (gc_type, pauses, phases) in groups {
+        let pause_avg = pauses.iter().sum::<f64>() / pauses.len().max(1) as f64;
+        println!("[{:?}] Pause avg {:.3} ms", gc_type, pause_avg);
+        for stats in phases {
+            let avg = stats.values.iter().sum::<f64>() / stats.values.len() as f64;
+            let max = stats.values.iter().copied().fold(0.0, f64::max);
+            let share = if pause_avg > 0.0 {
+                format!("{:>5.1}%", avg / pause_avg * 100.0)
+            } else {
+                "-".to_string()
+            };
+            print_row(
+                format!("{}{}", "  ".repeat(stats.depth + 1), stats.name),
+                format!("{:>10.3} ms (max {:.3} ms) {}", avg, max, share),
+            );
+        }
+    }
+}
+
+// 入れ子のパス ("/Evacuate Collection Set/Object Copy") ごとの所要時間
+struct PhaseStats<'a> {
+    path: String,
+    depth: usize,
+    name: &'a str,
+    values: Vec<f64>,
+}
+
+// 同じパスのフェーズをまとめる
+// 後から初めて現れた子フェーズも親の配下に並ぶよう、親のサブツリーの末尾に挿入する
+fn collect_phases<'a>(
+    phases: &'a [GcPhase],
+    parent: &str,
+    depth: usize,
+    out: &mut Vec<PhaseStats<'a>>,
+) {
+    // 合計の行を内訳に並べると割合が 100% を超えるので除く
+    for phase in phases.iter().filter(|p| !p.is_aggregate()) {
+        let path = format!("{}/{}", parent, phase.name);
+        match out.iter_mut().find(|stats| stats.path == path) {
+            Some(stats) => stats.values.push(phase.duration_ms),
+            None => {
+                let prefix = format!("{}/", parent);
+                let index = out
+                    .iter()
+                    .rposition(|stats| stats.path == parent || stats.path.starts_with(&prefix))
+                    .map_or(out.len(), |index| index + 1);
+                let stats = PhaseStats {
+                    path: path.clone(),
+                    depth,
+                    name: &phase.name,
+                    values: vec![phase.duration_ms],
+                };
+                out.insert(index, stats);
+            }
+        }
+        collect_phases(&phase.children, &path, depth + 1, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parser::parse_gc_events;
+    use regex::Regex;
+
+    // -Xlog:gc*:file=gc.log:none のように decorators を出力しないログ
+    fn events_without_decorators() -> Vec<GCEvent> {
+        let decorators = Regex::new(r"^(?:\[[^\]\[]*\])+\s*").unwrap();
+        let lines = include_str!("../../sample/sample_gc.log")
+            .lines()
+            .map(|line| decorators.replace(line, "").into_owned());
+        parse_gc_events(lines).unwrap().events
+    }
+
+    #[test]
+    fn falls_back_to_gc_id_axis_without_decorators() {
+        let events = events_without_decorators();
+        assert_eq!(events.len(), 34);
+        assert!(
+            events
+                .iter()
+                .all(|e| e.time.is_none() && e.uptime_secs.is_none())
+        );
+
+        let axis = TimeAxis::detect(&events);
+        assert!(matches!(axis, TimeAxis::GcId));
+        assert_eq!(axis.x(&events[0]), Some(0.0));
+        assert_eq!(axis.desc(), "GC id");
+
+        let path = std::env::temp_dir().join("gca_test_no_decorators.png");
+        let result = draw_combined_chart(&events, path.to_str().unwrap(), None);
+        let _ = std::fs::remove_file(&path);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn counts_evacuation_failures() {
+        let pause = |gc_type: GcType, failure: Option<EvacuationFailure>| GCEvent {
+            gc_type,
+            evacuation_failure: failure,
+            has_pause: true,
+            ..Default::default()
+        };
+        let concurrent = GCEvent {
+            gc_type: GcType::Concurrent,
+            ..Default::default()
+        };
+        let events = [
+            pause(GcType::Young, None),
+            pause(GcType::Young, Some(EvacuationFailure::ToSpaceExhausted)),
+            pause(GcType::Young, Some(EvacuationFailure::ToSpaceExhausted)),
+            // 並行サイクルを挟んでも、次の Pause が Full GC なら数える
+            concurrent,
+            pause(GcType::Full, None),
+            pause(GcType::Mixed, Some(EvacuationFailure::Pinned)),
+            pause(GcType::Young, None),
+        ];
+        let failures = count_evacuation_failures(&events);
+        assert_eq!(
+            failures.counts,
+            HashMap::from([
+                (EvacuationFailure::ToSpaceExhausted, 2),
+                (EvacuationFailure::Pinned, 1),
+            ])
+        );
+        assert_eq!(failures.followed_by_full, 1);
+        assert_eq!(
+            count_evacuation_failures(&events[..1]),
+            EvacuationFailureCounts::default()
+        );
+    }
+
+    #[test]
+    fn leaves_worker_total_out_of_phase_breakdown() {
+        let events = parse_gc_events(
+            include_str!("../../sample/sample_gc.log")
+                .lines()
+                .map(String::from),
+        )
+        .unwrap()
+        .events;
+        let event = events.iter().find(|e| e.gc_id == Some(1)).unwrap();
+        let mut phases = Vec::new();
+        collect_phases(&event.phases, "", 0, &mut phases);
+
+        assert!(phases.iter().all(|stats| stats.name != "GC Worker Total"));
+        let object_copy = phases
+            .iter()
+            .find(|stats| stats.name == "Object Copy")
+            .unwrap();
+        assert_eq!(object_copy.path, "/Evacuate Collection Set/Object Copy");
+        assert_eq!(object_copy.depth, 1);
+        let other = phases.iter().find(|stats| stats.name == "Other").unwrap();
+        assert_eq!((other.path.as_str(), other.depth), ("/Other", 0));
+
+        // 子フェーズは親の直後に並ぶ
+        let paths: Vec<&str> = phases.iter().map(|stats| stats.path.as_str()).collect();
+        let evacuate = paths
+            .iter()
+            .position(|p| *p == "/Evacuate Collection Set")
+            .unwrap();
+        assert_eq!(
+            paths[evacuate + 1],
+            "/Evacuate Collection Set/Ext Root Scanning"
+        );
+    }
+
+    #[test]
+    fn sorts_type_counts_by_count_then_name() {
+        let counts = HashMap::from([
+            (GcType::Cleanup, 9),
+            (GcType::Young, 16),
+            (GcType::Remark, 9),
+            (GcType::Mixed, 2),
+            (GcType::Full, 2),
+        ]);
+        let sorted = sorted_counts(&counts);
+        let sorted: Vec<(&str, usize)> = sorted
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                ("Young", 16),
+                ("Cleanup", 9),
+                ("Remark", 9),
+                ("Full", 2),
+                ("Mixed", 2),
+            ]
+        );
     }
 }
@@ -0,0 +1,589 @@
+use crate::model::{
+    ConcurrentCycle, DETECT_LINES, GCEvent, GcLog, GcType, LogFormat, LogInfo, detect_log_info,
+};
+use crate::util::input::is_stdin;
+use crate::util::parser::GcLogParser;
+use crate::util::report::{TIME_FORMAT, fmt_gc_id, fmt_time_label};
+use crate::util::units::MB;
+use crate::util::wrapper::LineUnwrapper;
+use std::fs::{File, Metadata};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+#[cfg(not(unix))]
+use std::time::UNIX_EPOCH;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// tail -F のようにGCログを開いたまま追記を読み、GCごとに1行のサマリを表示する
+// ローテーション (ファイルの差し替え) とトランケートを検知して読み直す
+// "-" の場合は標準入力 (kubectl logs -f 等のパイプ) を閉じられるまで読む
+pub fn follow_gc_log(path: &Path) -> Result<(), anyhow::Error> {
+    if is_stdin(path) {
+        return follow_stdin();
+    }
+    let mut follower = Follower::new(io::stdout());
+
+    let mut tail = Tail::open(path);
+    follower.read_available(&mut tail.reader)?;
+    follower.detect_format(true)?;
+    follower.flush_parser()?;
+    println!(
+        "📡 Following {} ({} GC events so far, Ctrl-C to stop)",
+        path.display(),
+        follower.stats.count
+    );
+    follower.live = true;
+
+    loop {
+        if !tail.poll(&mut follower)? {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+// 標準入力は読み込みがブロックするので、別スレッドで読んだ行を受け取り、
+// 追記が途切れたかどうかは受信のタイムアウトで判断する
+fn follow_stdin() -> Result<(), anyhow::Error> {
+    let (sender, receiver) = mpsc::channel::<io::Result<Vec<u8>>>();
+    thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        loop {
+            let mut line = Vec::new();
+            match stdin.read_until(b'\n', &mut line) {
+                Ok(0) => return,
+                read => {
+                    let failed = read.is_err();
+                    if sender.send(read.map(|_| line)).is_err() || failed {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+
+    println!("📡 Following stdin (Ctrl-C to stop)");
+    let mut follower = Follower::new(io::stdout());
+    follower.live = true;
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(line) => {
+                follower.pending = line?;
+                follower.flush_pending()?;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                follower.detect_format(false)?;
+                follower.flush_parser()?;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                follower.finish()?;
+                println!("📡 stdin was closed ({} GC events)", follower.stats.count);
+                return Ok(());
+            }
+        }
+    }
+}
+
+// 追っているファイルと、差し替えを見分けるための識別子
+struct Tail {
+    path: PathBuf,
+    reader: BufReader<File>,
+    id: Option<(u64, u64)>,
+}
+
+impl Tail {
+    fn open(path: &Path) -> Tail {
+        let (reader, id) = wait_for_file(path);
+        Tail {
+            path: path.to_path_buf(),
+            reader,
+            id,
+        }
+    }
+
+    // 追記を読み、無ければ保留中のイベントを確定させてからローテーションとトランケートを確認する
+    // 行を読んだかどうかを返す
+    fn poll<W: Write>(&mut self, follower: &mut Follower<W>) -> Result<bool, anyhow::Error> {
+        if follower.read_available(&mut self.reader)? > 0 {
+            return Ok(true);
+        }
+        follower.detect_format(false)?;
+        follower.flush_parser()?;
+
+        let Ok(meta) = self.path.metadata() else {
+            // ローテーション中でファイルが一時的に存在しない
+            return Ok(false);
+        };
+        let position = self.reader.stream_position()?;
+        if file_id(&meta) != self.id {
+            // 差し替え前のファイルに残っている行を読み切ってから新しいファイルに移る
+            follower.read_available(&mut self.reader)?;
+            follower.flush_pending()?;
+            eprintln!("🔄 {} was rotated, reopening", self.path.display());
+            (self.reader, self.id) = wait_for_file(&self.path);
+        } else if meta.len() < position {
+            eprintln!(
+                "✂️ {} was truncated, reading from the start",
+                self.path.display()
+            );
+            follower.reset()?;
+            self.reader.seek(SeekFrom::Start(0))?;
+        }
+        Ok(false)
+    }
+}
+
+fn wait_for_file(path: &Path) -> (BufReader<File>, Option<(u64, u64)>) {
+    let mut warned = false;
+    loop {
+        if let Ok(file) = File::open(path) {
+            let id = file.metadata().ok().and_then(|meta| file_id(&meta));
+            return (BufReader::new(file), id);
+        }
+        if !warned {
+            eprintln!("⏳ Waiting for {} to appear", path.display());
+            warned = true;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+// ファイルの同一性 (デバイス番号と inode)
+#[cfg(unix)]
+fn file_id(meta: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+// inode が無い環境では作成日時で差し替えを見分ける
+#[cfg(not(unix))]
+fn file_id(meta: &Metadata) -> Option<(u64, u64)> {
+    let created = meta.created().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((created.as_secs(), u64::from(created.subsec_nanos())))
+}
+
+#[derive(Debug)]
+struct Follower<W: Write> {
+    // 改行まで書き込まれていない行
+    pending: Vec<u8>,
+    unwrapper: LineUnwrapper,
+    // ログ形式の判定が終わるまでの先頭行
+    head: Vec<String>,
+    parser: Option<GcLogParser>,
+    stats: RollingStats,
+    // 起動時点の既存ログを読み終えた後は、イベントごとに表示する
+    live: bool,
+    // トランケート後にパーサを作り直すための判定結果
+    info: Option<LogInfo>,
+    out: W,
+}
+
+impl<W: Write> Follower<W> {
+    fn new(out: W) -> Self {
+        Follower {
+            pending: Vec::new(),
+            unwrapper: LineUnwrapper::default(),
+            head: Vec::new(),
+            parser: None,
+            stats: RollingStats::default(),
+            live: false,
+            info: None,
+            out,
+        }
+    }
+
+    // 読み込める完全な行をすべて処理し、読んだ行数を返す
+    fn read_available<R: BufRead>(&mut self, reader: &mut R) -> Result<usize, anyhow::Error> {
+        let mut count = 0;
+        loop {
+            if reader.read_until(b'\n', &mut self.pending)? == 0 || !self.pending.ends_with(b"\n") {
+                return Ok(count);
+            }
+            self.flush_pending()?;
+            count += 1;
+        }
+    }
+
+    // トランケートされたファイルは先頭から別の内容になるので、書きかけの行と
+    // 確定していないイベント・並行サイクルを捨ててパーサを作り直す
+    fn reset(&mut self) -> io::Result<()> {
+        self.flush_parser()?;
+        self.pending.clear();
+        self.unwrapper = LineUnwrapper::default();
+        if let Some(info) = &self.info {
+            self.parser = Some(GcLogParser::new(info));
+        }
+        Ok(())
+    }
+
+    fn flush_pending(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let line = String::from_utf8_lossy(&self.pending)
+            .trim_end_matches(['\r', '\n'])
+            .to_string();
+        self.pending.clear();
+        match self.unwrapper.unwrap(&line) {
+            Some(line) => self.feed(line),
+            None => Ok(()),
+        }
+    }
+
+    fn feed(&mut self, line: String) -> io::Result<()> {
+        let Some(parser) = self.parser.as_mut() else {
+            self.head.push(line);
+            if self.head.len() >= DETECT_LINES {
+                self.detect_format(true)?;
+            }
+            return Ok(());
+        };
+        let batch = parser.feed(line);
+        self.show(batch)
+    }
+
+    // 追記が途切れたら、[gc,cpu] の行を待っているイベントも表示する
+    fn flush_parser(&mut self) -> io::Result<()> {
+        match self.parser.as_mut() {
+            Some(parser) => {
+                let batch = parser.flush();
+                self.show(batch)
+            }
+            None => Ok(()),
+        }
+    }
+
+    // 入力が閉じられたら、書きかけの行と読みかけのイベントもすべて確定させる
+    fn finish(&mut self) -> io::Result<()> {
+        self.flush_pending()?;
+        self.detect_format(true)?;
+        match self.parser.take() {
+            Some(parser) => self.show(parser.finish()),
+            None => Ok(()),
+        }
+    }
+
+    fn show(&mut self, batch: GcLog) -> io::Result<()> {
+        for event in &batch.events {
+            self.stats.push(event);
+            if self.live {
+                writeln!(self.out, "{}", format_event_line(event, &self.stats))?;
+            }
+        }
+        if self.live {
+            for cycle in &batch.cycles {
+                writeln!(self.out, "{}", format_cycle_line(cycle))?;
+            }
+        }
+        Ok(())
+    }
+
+    // 先頭行からログ形式を判定してパーサを作る
+    // 追記待ちの間は、形式が分かるまで判定を保留する
+    fn detect_format(&mut self, force: bool) -> io::Result<()> {
+        if self.parser.is_some() || self.head.is_empty() {
+            return Ok(());
+        }
+        let info = detect_log_info(&self.head);
+        if info.format == LogFormat::Unknown && !force {
+            return Ok(());
+        }
+        writeln!(
+            self.out,
+            "🔍 Format: {:?} / Collector: {:?}",
+            info.format, info.collector
+        )?;
+        self.parser = Some(GcLogParser::new(&info));
+        self.info = Some(info);
+        for line in std::mem::take(&mut self.head) {
+            self.feed(line)?;
+        }
+        Ok(())
+    }
+}
+
+// これまでのGCの件数と Pause 時間の集計
+#[derive(Debug, Default)]
+struct RollingStats {
+    count: usize,
+    pauses: usize,
+    total_pause_ms: f64,
+    max_pause_ms: f64,
+}
+
+impl RollingStats {
+    fn push(&mut self, event: &GCEvent) {
+        self.count += 1;
+        if let Some(pause) = event.pause_time_ms {
+            self.pauses += 1;
+            self.total_pause_ms += pause;
+            self.max_pause_ms = self.max_pause_ms.max(pause);
+        }
+    }
+
+    fn avg_pause_ms(&self) -> f64 {
+        if self.pauses == 0 {
+            0.0
+        } else {
+            self.total_pause_ms / self.pauses as f64
+        }
+    }
+}
+
+// "09:50:19.433 GC(12) Young        EvacuationPause          21.425 ms  307.0M->17.0M(6144.0M) | 13 GCs, avg 15.200 ms, max 40.100 ms"
+fn format_event_line(event: &GCEvent, stats: &RollingStats) -> String {
+    let time = fmt_time_label(event.time, event.uptime_secs, TIME_FORMAT);
+    let id = fmt_gc_id(event.gc_id);
+    let cause = event
+        .gc_cause
+        .as_ref()
+        .map(|cause| format!("{:?}", cause))
+        .unwrap_or_else(|| "-".to_string());
+    let pause = event
+        .pause_time_ms
+        .map(|pause| format!("{:>9.3} ms", pause))
+        .unwrap_or_else(|| format!("{:>12}", "-"));
+    let heap = match (event.heap_used_before, event.heap_used_after) {
+        (Some(before), Some(after)) => {
+            let committed = event
+                .heap_committed
+                .map(|committed| format!("({:.1}M)", committed / MB))
+                .unwrap_or_default();
+            format!("{:.1}M->{:.1}M{}", before / MB, after / MB, committed)
+        }
+        _ => "-".to_string(),
+    };
+    let warning = if event.gc_type == GcType::Full || event.gc_type == GcType::Degenerated {
+        " ⚠️"
+    } else {
+        ""
+    };
+    format!(
+        "{} {:<8} {:<12} {:<22}{} {}{} | {} GCs, avg {:.3} ms, max {:.3} ms",
+        time,
+        id,
+        format!("{:?}", event.gc_type),
+        cause,
+        pause,
+        heap,
+        warning,
+        stats.count,
+        stats.avg_pause_ms(),
+        stats.max_pause_ms
+    )
+}
+
+// "09:50:22.222 GC(5)   Concurrent Cycle 47.991 ms (6 phases)"
+fn format_cycle_line(cycle: &ConcurrentCycle) -> String {
+    let time = fmt_time_label(cycle.end, cycle.end_uptime_secs, TIME_FORMAT);
+    let id = fmt_gc_id(cycle.gc_id);
+    let duration = cycle
+        .duration_ms
+        .map(|duration| format!("{:.3} ms", duration))
+        .unwrap_or_else(|| "-".to_string());
+    format!(
+        "{} {:<8} Concurrent Cycle {} ({} phases){}",
+        time,
+        id,
+        duration,
+        cycle.phases.len(),
+        if cycle.aborted { " ⚠️ aborted" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const SAMPLE: &str = include_str!("../../sample/sample_gc.log");
+
+    // sample_gc.log の from 行目から to 行目まで (1始まり)
+    fn sample_lines(from: usize, to: usize) -> String {
+        SAMPLE
+            .lines()
+            .skip(from - 1)
+            .take(to + 1 - from)
+            .map(|line| format!("{}\n", line))
+            .collect()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gca-follow-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn live_follower() -> Follower<Vec<u8>> {
+        let mut follower = Follower::new(Vec::new());
+        follower.live = true;
+        follower
+    }
+
+    // 追記が途切れるまでポーリングする
+    fn poll_until_idle(tail: &mut Tail, follower: &mut Follower<Vec<u8>>) {
+        while tail.poll(follower).unwrap() {}
+    }
+
+    fn printed(follower: &Follower<Vec<u8>>) -> Vec<String> {
+        String::from_utf8(follower.out.clone())
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    const GC0: &str = "09:50:19.455 GC(0)    Young        EvacuationPause          21.425 ms 307.0M->17.0M(6144.0M)";
+    const GC1: &str = "09:50:20.252 GC(1)    Young        EvacuationPause          17.177 ms 305.0M->20.0M(6144.0M)";
+    const GC2: &str = "09:50:20.909 GC(2)    Young        MetadataGcThreshold      13.334 ms 184.0M->18.0M(6144.0M)";
+
+    #[test]
+    fn prints_a_line_per_gc() {
+        let mut follower = live_follower();
+        for line in SAMPLE.lines().take(217) {
+            follower.feed(line.to_string()).unwrap();
+        }
+        follower.detect_format(true).unwrap();
+        follower.flush_parser().unwrap();
+        let lines = printed(&follower);
+        assert_eq!(lines[0], "🔍 Format: Unified / Collector: G1");
+        assert_eq!(
+            lines[1],
+            format!("{} | 1 GCs, avg 21.425 ms, max 21.425 ms", GC0)
+        );
+        assert_eq!(
+            lines[2],
+            format!("{} | 2 GCs, avg 19.301 ms, max 21.425 ms", GC1)
+        );
+        assert_eq!(
+            lines[3],
+            format!("{} | 3 GCs, avg 17.312 ms, max 21.425 ms", GC2)
+        );
+        assert_eq!(
+            &lines[4..],
+            [
+                "09:50:20.923 GC(3)    Remark       -                         3.618 ms 21.0M->21.0M(6144.0M) | 4 GCs, avg 13.889 ms, max 21.425 ms",
+                "09:50:20.923 GC(3)    Cleanup      -                         0.257 ms 21.0M->21.0M(6144.0M) | 5 GCs, avg 11.162 ms, max 21.425 ms",
+                "09:50:20.946 GC(3)    Concurrent Cycle 37.466 ms (6 phases)",
+            ]
+        );
+    }
+
+    #[test]
+    fn reopens_rotated_file() {
+        let dir = temp_dir("rotate");
+        let path = dir.join("gc.log");
+        fs::write(&path, sample_lines(1, 116)).unwrap();
+
+        let mut follower = live_follower();
+        let mut tail = Tail::open(&path);
+        poll_until_idle(&mut tail, &mut follower);
+        assert_eq!(follower.stats.count, 2);
+
+        // JVM は差し替え前のファイルに GC(2) の前半を書いてから、新しいファイルに移る
+        fs::rename(&path, dir.join("gc.log.0")).unwrap();
+        let mut rotated = fs::OpenOptions::new()
+            .append(true)
+            .open(dir.join("gc.log.0"))
+            .unwrap();
+        rotated
+            .write_all(sample_lines(117, 150).as_bytes())
+            .unwrap();
+        fs::write(&path, sample_lines(151, 172)).unwrap();
+        poll_until_idle(&mut tail, &mut follower);
+        assert_eq!(tail.id, file_id(&path.metadata().unwrap()));
+        poll_until_idle(&mut tail, &mut follower);
+
+        let lines = printed(&follower);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with(GC0));
+        assert!(lines[2].starts_with(GC1));
+        assert_eq!(
+            lines[3],
+            format!("{} | 3 GCs, avg 17.312 ms, max 21.425 ms", GC2)
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rereads_truncated_file_from_the_start() {
+        let dir = temp_dir("truncate");
+        let path = dir.join("gc.log");
+        // GC(1) の [gc,cpu] より前で止まっているところをトランケートされる
+        fs::write(&path, sample_lines(1, 114)).unwrap();
+
+        let mut follower = live_follower();
+        let mut tail = Tail::open(&path);
+        poll_until_idle(&mut tail, &mut follower);
+        assert_eq!(follower.stats.count, 1);
+
+        fs::write(&path, sample_lines(1, 61)).unwrap();
+        assert!(!tail.poll(&mut follower).unwrap());
+        poll_until_idle(&mut tail, &mut follower);
+
+        // 読みかけだった GC(1) は捨てられ、先頭から読み直した GC(0) が表示される
+        let lines = printed(&follower);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with(GC0));
+        assert_eq!(
+            lines[2],
+            format!("{} | 2 GCs, avg 21.425 ms, max 21.425 ms", GC0)
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_rolling_pause_stats() {
+        let mut stats = RollingStats::default();
+        for pause in [Some(10.0), None, Some(20.0)] {
+            stats.push(&GCEvent {
+                pause_time_ms: pause,
+                ..Default::default()
+            });
+        }
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.pauses, 2);
+        assert_eq!(stats.avg_pause_ms(), 15.0);
+        assert_eq!(stats.max_pause_ms, 20.0);
+        assert_eq!(RollingStats::default().avg_pause_ms(), 0.0);
+    }
+
+    #[test]
+    fn formats_full_gc_and_aborted_cycle() {
+        let stats = RollingStats {
+            count: 7,
+            pauses: 6,
+            total_pause_ms: 900.0,
+            max_pause_ms: 812.451,
+        };
+        let full = GCEvent {
+            uptime_secs: Some(5.311),
+            gc_type: GcType::Full,
+            gc_cause: Some(crate::model::GcCause::AllocationFailure),
+            pause_time_ms: Some(812.451),
+            heap_used_before: Some(494050.0 * 1024.0),
+            heap_used_after: Some(151023.0 * 1024.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_event_line(&full, &stats),
+            "5.311s          Full         AllocationFailure       812.451 ms 482.5M->147.5M ⚠️ | 7 GCs, avg 150.000 ms, max 812.451 ms"
+        );
+
+        let cycle = ConcurrentCycle {
+            gc_id: Some(5),
+            end_uptime_secs: Some(4.9),
+            duration_ms: Some(47.991),
+            phases: vec![Default::default(); 2],
+            aborted: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_cycle_line(&cycle),
+            "4.900s GC(5)    Concurrent Cycle 47.991 ms (2 phases) ⚠️ aborted"
+        );
+    }
+}
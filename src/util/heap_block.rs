@@ -0,0 +1,262 @@
+use crate::model::{GCEvent, GcType};
+use crate::util::parser::parse_size;
+use crate::util::units::MB;
+use regex::Regex;
+use std::sync::LazyLock;
+
+// debug の "GC(0) Heap before GC invocations=0 (full 0): garbage-first heap   total 6291456K, used 315092K [..."
+// Parallel / Serial / CMS は次の行から世代ごとに "PSYoungGen      total 153600K, used 132734K [..." と続く
+static RE_BLOCK_START: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"GC\(\d+\) Heap (before|after) GC invocations=(\d+) \(full (\d+)\):").unwrap()
+});
+// 世代の名前はコレクタごとに異なる ("par new generation", "concurrent mark-sweep generation" 等)
+static RE_SPACE_USAGE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\w-] +total (\d+)K, used (\d+)K").unwrap());
+// "GC(0)   region size 4096K, 76 young (311296K), 0 survivors (0K)"
+static RE_BLOCK_REGIONS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"region size \d+K, (\d+) young \(\d+K\), (\d+) survivors \(\d+K\)").unwrap()
+});
+
+// サマリ行のヒープ使用量は M 単位に切り捨てて出力される
+const SUMMARY_TOLERANCE_BYTES: f64 = MB;
+// GC前のサマリ行は割り当て中のリージョンの分を含むので、ブロックの値より大きくなることがある
+const SUMMARY_BEFORE_SLACK: f64 = 0.1;
+
+// Heap before/after GC ブロックの開始行なら、after ブロックかどうかを返す
+pub fn heap_block_kind(line: &str) -> Option<bool> {
+    RE_BLOCK_START
+        .captures(line)
+        .map(|caps| &caps[1] == "after")
+}
+
+// G1 は Heap before GC を出力してから invocations を進める
+// Parallel / Serial は進めてから出力するので、before と after が同じ値になる
+pub fn counts_after_heap_before(event: &GCEvent) -> bool {
+    event.block_young_regions_before.is_some() || event.block_young_regions_after.is_some()
+}
+
+// ヒープ全体・世代ごとの "total 6291456K, used 315092K" (bytes)
+// JVM 終了時の Heap ブロックも同じ形式で出力される
+pub fn parse_space_usage(line: &str) -> Option<(f64, f64)> {
+    let caps = RE_SPACE_USAGE.captures(line)?;
+    Some((parse_size(&caps[1], "K")?, parse_size(&caps[2], "K")?))
+}
+
+// G1 の young / survivors のリージョン数
+pub fn parse_block_regions(line: &str) -> Option<(f64, f64)> {
+    let caps = RE_BLOCK_REGIONS.captures(line)?;
+    Some((caps[1].parse().ok()?, caps[2].parse().ok()?))
+}
+
+// Heap before/after GC ブロックの行をイベントに反映する
+// after_gc は Heap after GC ブロックの中の行かどうか
+pub fn apply_heap_block_line(event: &mut GCEvent, line: &str, after_gc: bool) {
+    if let Some(caps) = RE_BLOCK_START.captures(line) {
+        let invocations = caps[2].parse().ok();
+        let full = caps[3].parse().ok();
+        if after_gc {
+            event.invocations_after = invocations;
+            event.full_invocations_after = full;
+            event.block_heap_total_after = Some(0.0);
+            event.block_heap_used_after = Some(0.0);
+        } else {
+            event.invocations_before = invocations;
+            event.full_invocations_before = full;
+            event.block_heap_total_before = Some(0.0);
+            event.block_heap_used_before = Some(0.0);
+        }
+    }
+
+    // 世代別のコレクタは各世代の合計をヒープ全体とする
+    if let Some((space_total, space_used)) = parse_space_usage(line) {
+        let (total, used) = if after_gc {
+            (
+                &mut event.block_heap_total_after,
+                &mut event.block_heap_used_after,
+            )
+        } else {
+            (
+                &mut event.block_heap_total_before,
+                &mut event.block_heap_used_before,
+            )
+        };
+        *total.get_or_insert(0.0) += space_total;
+        *used.get_or_insert(0.0) += space_used;
+    } else if let Some((young, survivors)) = parse_block_regions(line) {
+        if after_gc {
+            event.block_young_regions_after = Some(young);
+            event.block_survivor_regions_after = Some(survivors);
+        } else {
+            event.block_young_regions_before = Some(young);
+            event.block_survivor_regions_before = Some(survivors);
+        }
+    }
+}
+
+// Heap before/after GC ブロックの値を、サマリ行・リージョン行の値と突き合わせる
+// 単位を揃える前 (bytes / リージョン数) のイベントに対して呼ぶ
+pub fn check_heap_block(event: &GCEvent) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    // young はEden + Survivor のリージョン数
+    let young_pairs = [
+        (
+            "before",
+            event.block_young_regions_before,
+            event.eden_before.zip(event.survivor_before),
+        ),
+        (
+            "after",
+            event.block_young_regions_after,
+            event.eden_after.zip(event.survivor_after),
+        ),
+    ];
+    for (when, young, regions) in young_pairs {
+        if let (Some(young), Some((eden, survivor))) = (young, regions)
+            && young != eden + survivor
+        {
+            issues.push(format!(
+                "young regions {} {} != eden {} + survivor {}",
+                when, young, eden, survivor
+            ));
+        }
+    }
+    let survivor_pairs = [
+        (
+            "before",
+            event.block_survivor_regions_before,
+            event.survivor_before,
+        ),
+        (
+            "after",
+            event.block_survivor_regions_after,
+            event.survivor_after,
+        ),
+    ];
+    for (when, block, summary) in survivor_pairs {
+        if let (Some(block), Some(summary)) = (block, summary)
+            && block != summary
+        {
+            issues.push(format!(
+                "survivor regions {} {} != {}",
+                when, block, summary
+            ));
+        }
+    }
+
+    let used_pairs = [
+        (
+            "before",
+            event.block_heap_used_before,
+            event.heap_used_before,
+            SUMMARY_BEFORE_SLACK,
+        ),
+        (
+            "after",
+            event.block_heap_used_after,
+            event.heap_used_after,
+            0.0,
+        ),
+    ];
+    for (when, block, summary, slack) in used_pairs {
+        if let (Some(block), Some(summary)) = (block, summary)
+            && (block - summary >= SUMMARY_TOLERANCE_BYTES
+                || summary - block >= SUMMARY_TOLERANCE_BYTES + block * slack)
+        {
+            issues.push(format!(
+                "heap used {} {:.0}K != summary {:.0}K",
+                when,
+                block / 1024.0,
+                summary / 1024.0
+            ));
+        }
+    }
+
+    let step = u64::from(counts_after_heap_before(event));
+    if let (Some(before), Some(after)) = (event.invocations_before, event.invocations_after)
+        && after != before + step
+    {
+        issues.push(format!("invocations {} -> {}", before, after));
+    }
+    if let (Some(before), Some(after)) =
+        (event.full_invocations_before, event.full_invocations_after)
+    {
+        let expected = before + step * u64::from(event.gc_type == GcType::Full);
+        if after != expected {
+            issues.push(format!("full invocations {} -> {}", before, after));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse_gc_events;
+
+    const SAMPLE: &str = include_str!("../../sample/sample_gc.log");
+
+    // sample_gc.log の GC(0) (Heap before / after GC ブロック付きの Pause Young) を書き換えて解析する
+    fn first_gc_issues(edits: &[(&str, &str)]) -> Vec<String> {
+        let lines = SAMPLE.lines().take(61).map(|line| {
+            edits
+                .iter()
+                .fold(line.to_string(), |line, (from, to)| line.replace(from, to))
+        });
+        let log = parse_gc_events(lines).unwrap();
+        let event = &log.events[0];
+        assert_eq!(event.gc_id, Some(0));
+        event.heap_block_issues.clone()
+    }
+
+    #[test]
+    fn accepts_consistent_block() {
+        assert!(first_gc_issues(&[]).is_empty());
+        // GC前のサマリ行 (307M) は割り当て中のリージョンの分だけブロックより大きくてよい
+        assert!(first_gc_issues(&[("used 315092K", "used 300000K")]).is_empty());
+    }
+
+    #[test]
+    fn reports_inconsistent_block() {
+        let issues = first_gc_issues(&[
+            ("76 young (311296K)", "75 young (307200K)"),
+            ("4 survivors (16384K)", "3 survivors (12288K)"),
+            ("used 315092K", "used 320000K"),
+            ("used 18349K", "used 40000K"),
+            ("invocations=1 (full 0)", "invocations=2 (full 1)"),
+        ]);
+        assert_eq!(
+            issues,
+            [
+                "young regions before 75 != eden 76 + survivor 0",
+                "survivor regions after 3 != 4",
+                "heap used before 320000K != summary 314368K",
+                "heap used after 40000K != summary 17408K",
+                "invocations 0 -> 2",
+                "full invocations 0 -> 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_full_invocations_for_full_gc() {
+        let mut event = GCEvent {
+            gc_type: GcType::Full,
+            block_young_regions_before: Some(12.0),
+            invocations_before: Some(7),
+            invocations_after: Some(8),
+            full_invocations_before: Some(0),
+            full_invocations_after: Some(1),
+            ..Default::default()
+        };
+        assert!(check_heap_block(&event).is_empty());
+
+        event.full_invocations_after = Some(0);
+        assert_eq!(check_heap_block(&event), ["full invocations 0 -> 0"]);
+
+        // Parallel / Serial は before と after が同じ値になる
+        event.block_young_regions_before = None;
+        event.invocations_after = Some(7);
+        assert!(check_heap_block(&event).is_empty());
+    }
+}
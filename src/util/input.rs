@@ -0,0 +1,435 @@
+use crate::model::DETECT_LINES;
+use crate::util::decorator::parse_decorations;
+use crate::util::parser::{LogLines, parse_gc_time, read_lines, read_stdin_lines};
+use crate::util::wrapper::unwrap_log_lines;
+use anyhow::{Context, bail};
+use chrono::{DateTime, FixedOffset};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+// JDK 8 形式の行頭 "2025-04-11T09:50:19.433+0900: 2.127: "
+// (-XX:+PrintGCDateStamps だけの場合は経過秒が無い)
+static RE_LEGACY_STAMP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{3}[+-]\d{4}): )?(?:(\d+\.\d+): )?\[")
+        .unwrap()
+});
+
+// "--input -" で標準入力から読む
+pub const STDIN_PATH: &str = "-";
+
+// --input に指定されたファイル・ディレクトリ・globを展開し、
+// ローテーションされたセグメントを時系列に並べて1本の行ストリームとして返す
+// コンテナやsyslogのラッパーはここで剥がしておく
+pub fn read_log_set(inputs: &[String]) -> Result<impl Iterator<Item = String>, anyhow::Error> {
+    let paths = order_segments(resolve_inputs(inputs)?)?;
+    let mut readers = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let lines = if is_stdin(path) {
+            read_stdin_lines().context("failed to read stdin")?
+        } else {
+            read_lines(path).with_context(|| format!("failed to open {}", path.display()))?
+        };
+        readers.push((path.clone(), lines));
+    }
+    Ok(unwrap_log_lines(
+        readers
+            .into_iter()
+            .flat_map(|(path, lines)| readable_lines(path, lines)),
+    ))
+}
+
+// 読み込みエラーは警告して、そのセグメントの残りを読み飛ばす
+// (壊れた圧縮データは読み直しても回復しないので、次のセグメントに進む)
+fn readable_lines(path: PathBuf, lines: LogLines) -> impl Iterator<Item = String> {
+    lines.map_while(move |line| {
+        line.map_err(|err| eprintln!("⚠️ Failed to read {}: {}", path.display(), err))
+            .ok()
+    })
+}
+
+pub fn resolve_inputs(inputs: &[String]) -> Result<Vec<PathBuf>, anyhow::Error> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        if input == STDIN_PATH {
+            paths.push(path.to_path_buf());
+        } else if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_file() && !is_hidden(p))
+                .collect();
+            entries.sort();
+            paths.extend(entries);
+        } else if !path.exists() && input.contains(['*', '?', '[']) {
+            let mut matched: Vec<PathBuf> = glob::glob(input)?
+                .filter_map(Result::ok)
+                .filter(|p| p.is_file())
+                .collect();
+            if matched.is_empty() {
+                bail!("no files match {}", input);
+            }
+            matched.sort();
+            paths.extend(matched);
+        } else {
+            paths.push(path.to_path_buf());
+        }
+    }
+
+    // ディレクトリとglobの両方に含まれるファイルは一度だけ読む
+    let mut unique: Vec<PathBuf> = Vec::with_capacity(paths.len());
+    for path in paths {
+        if !unique.contains(&path) {
+            unique.push(path);
+        }
+    }
+    if unique.is_empty() {
+        bail!("no input files found");
+    }
+    Ok(unique)
+}
+
+pub fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == STDIN_PATH
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+// セグメントを先頭のタイムスタンプで並べ替える
+// 1. 全セグメントに壁時計時刻があればその順
+// 2. 無ければ先頭の経過秒の順 (デフォルトの decorators "uptime,level,tags" ではこちらになる)
+// 3. それも無ければ "gc.log.N" のローテーション番号の順
+// ローテーション番号は一周すると古い順にならないので、同じ時刻のセグメントの順序を決めるのにだけ使う
+// どのキーも揃わなければ指定順のまま読む
+fn order_segments(paths: Vec<PathBuf>) -> Result<Vec<PathBuf>, anyhow::Error> {
+    if paths.len() < 2 {
+        return Ok(paths);
+    }
+    let mut keyed: Vec<(SegmentKey, PathBuf)> = Vec::with_capacity(paths.len());
+    for path in paths {
+        let key =
+            segment_key(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        keyed.push((key, path));
+    }
+
+    let rotation: Option<Vec<f64>> = rotation_order(keyed.iter().map(|(_, path)| path.as_path()));
+    let sort_keys: Option<Vec<f64>> = keyed
+        .iter()
+        .map(|(key, _)| key.time)
+        .collect::<Option<Vec<f64>>>()
+        .or_else(|| keyed.iter().map(|(key, _)| key.uptime).collect())
+        .or_else(|| rotation.clone());
+    let Some(sort_keys) = sort_keys else {
+        return Ok(keyed.into_iter().map(|(_, path)| path).collect());
+    };
+    let tie_breaks = rotation.unwrap_or_else(|| vec![0.0; sort_keys.len()]);
+
+    let mut sorted: Vec<((f64, f64), PathBuf)> = sort_keys
+        .into_iter()
+        .zip(tie_breaks)
+        .zip(keyed.into_iter().map(|(_, path)| path))
+        .collect();
+    sorted.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    Ok(sorted.into_iter().map(|(_, path)| path).collect())
+}
+
+// セグメントの先頭の壁時計時刻 (エポック秒) と経過秒
+#[derive(Debug, Default, PartialEq)]
+struct SegmentKey {
+    time: Option<f64>,
+    uptime: Option<f64>,
+}
+
+// 標準入力は読み直せないので並べ替えのキーを持たない
+// 先頭を読めない (壊れた圧縮データ等) セグメントは並べ替えられないのでエラーにする
+fn segment_key(path: &Path) -> Result<SegmentKey, io::Error> {
+    if is_stdin(path) {
+        return Ok(SegmentKey::default());
+    }
+    let lines = read_lines(path)?
+        .take(DETECT_LINES)
+        .collect::<Result<Vec<String>, io::Error>>()?;
+    for line in unwrap_log_lines(lines) {
+        let (decorations, _) = parse_decorations(&line);
+        if decorations.time.is_some() || decorations.uptime_secs.is_some() {
+            return Ok(SegmentKey {
+                time: decorations.time.map(epoch_secs),
+                uptime: decorations.uptime_secs,
+            });
+        }
+        if let Some(caps) = RE_LEGACY_STAMP.captures(&line) {
+            let time = caps.get(1).and_then(|m| parse_gc_time(m.as_str()));
+            let uptime = caps.get(2).and_then(|m| m.as_str().parse().ok());
+            if time.is_some() || uptime.is_some() {
+                return Ok(SegmentKey {
+                    time: time.map(epoch_secs),
+                    uptime,
+                });
+            }
+        }
+    }
+    Ok(SegmentKey::default())
+}
+
+fn epoch_secs(time: DateTime<FixedOffset>) -> f64 {
+    time.timestamp_millis() as f64 / 1000.0
+}
+
+// 全セグメントが同じログのローテーション ("gc.log.0", "gc.log.1.gz", JDK 8 の "gc.log.2.current") なら番号を返す
+// 番号の付いていない "gc.log" は書き込み中のセグメントなので最後に置く
+fn rotation_order<'a>(paths: impl Iterator<Item = &'a Path>) -> Option<Vec<f64>> {
+    let mut base_name: Option<String> = None;
+    let mut order = Vec::new();
+    for path in paths {
+        let (base, index) = rotation_index(path)?;
+        if base_name.get_or_insert_with(|| base.clone()) != &base {
+            return None;
+        }
+        order.push(index.map_or(f64::INFINITY, |index| index as f64));
+    }
+    Some(order)
+}
+
+fn rotation_index(path: &Path) -> Option<(String, Option<u64>)> {
+    if is_stdin(path) {
+        return None;
+    }
+    let name = path.file_name()?.to_str()?;
+    let name = name
+        .strip_suffix(".gz")
+        .or_else(|| name.strip_suffix(".zst"))
+        .unwrap_or(name);
+    let name = name.strip_suffix(".current").unwrap_or(name);
+    match name.rsplit_once('.') {
+        Some((base, index)) if index.bytes().all(|b| b.is_ascii_digit()) => {
+            Some((base.to_string(), Some(index.parse().ok()?)))
+        }
+        _ => Some((name.to_string(), None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse_gc_events;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    const SAMPLE: &str = include_str!("../../sample/sample_gc.log");
+
+    // sample_gc.log の from 行目から to 行目まで
+    fn sample_lines(from: usize, to: usize) -> String {
+        SAMPLE
+            .lines()
+            .skip(from - 1)
+            .take(to + 1 - from)
+            .map(|line| format!("{}\n", line))
+            .collect()
+    }
+
+    // 壁時計時刻の decorator を外して経過秒だけにする
+    fn uptime_only(text: &str) -> String {
+        text.lines()
+            .map(|line| format!("{}\n", line.split_once(']').map_or(line, |(_, rest)| rest)))
+            .collect()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gca-input-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_gzip(path: &Path, text: &str) {
+        let mut encoder = GzEncoder::new(fs::File::create(path).unwrap(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    fn input_names(paths: &[PathBuf]) -> Vec<String> {
+        paths.iter().map(|p| p.display().to_string()).collect()
+    }
+
+    fn file_names(paths: &[PathBuf]) -> Vec<&str> {
+        paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect()
+    }
+
+    fn gc_ids(inputs: &[PathBuf]) -> Vec<Option<u64>> {
+        let inputs = input_names(inputs);
+        parse_gc_events(read_log_set(&inputs).unwrap())
+            .unwrap()
+            .events
+            .iter()
+            .map(|e| e.gc_id)
+            .collect()
+    }
+
+    #[test]
+    fn resolves_directories_globs_and_duplicates() {
+        let dir = temp_dir("resolve");
+        for name in ["gc.log.1", "gc.log.0", "app.log", ".gc.log.swp"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        fs::create_dir(dir.join("archive")).unwrap();
+
+        // ディレクトリは隠しファイルとサブディレクトリを除いて名前順
+        let paths = resolve_inputs(&input_names(std::slice::from_ref(&dir))).unwrap();
+        assert_eq!(file_names(&paths), ["app.log", "gc.log.0", "gc.log.1"]);
+
+        // ディレクトリとglobで重なったファイルは最初の1回だけ
+        let glob = dir.join("gc.log.*").display().to_string();
+        let inputs = vec![glob, dir.display().to_string()];
+        let paths = resolve_inputs(&inputs).unwrap();
+        assert_eq!(file_names(&paths), ["gc.log.0", "gc.log.1", "app.log"]);
+
+        let missing = dir.join("nothing.*").display().to_string();
+        let err = resolve_inputs(std::slice::from_ref(&missing)).unwrap_err();
+        assert_eq!(err.to_string(), format!("no files match {}", missing));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reads_gzip_and_zstd_segments() {
+        let dir = temp_dir("compressed");
+        let text = sample_lines(1, 116);
+        write_gzip(&dir.join("gc.log.gz"), &text);
+        fs::write(
+            dir.join("gc.log.zst"),
+            zstd::encode_all(text.as_bytes(), 0).unwrap(),
+        )
+        .unwrap();
+
+        for name in ["gc.log.gz", "gc.log.zst"] {
+            let inputs = input_names(&[dir.join(name)]);
+            let lines: Vec<String> = read_log_set(&inputs).unwrap().collect();
+            assert_eq!(lines, text.lines().collect::<Vec<_>>(), "{}", name);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn merges_shuffled_rotation_into_one_stream() {
+        let dir = temp_dir("rotation");
+        fs::write(dir.join("gc.log.0"), sample_lines(1, 61)).unwrap();
+        fs::write(
+            dir.join("gc.log.1.zst"),
+            zstd::encode_all(sample_lines(62, 116).as_bytes(), 0).unwrap(),
+        )
+        .unwrap();
+        write_gzip(&dir.join("gc.log.2.gz"), &sample_lines(117, 1310));
+
+        let shuffled = [
+            dir.join("gc.log.2.gz"),
+            dir.join("gc.log.0"),
+            dir.join("gc.log.1.zst"),
+        ];
+        let whole = parse_gc_events(SAMPLE.lines().map(String::from)).unwrap();
+        let expected: Vec<Option<u64>> = whole.events.iter().map(|e| e.gc_id).collect();
+        assert_eq!(gc_ids(&shuffled), expected);
+        assert!(expected.windows(2).all(|w| w[0] <= w[1]));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_uptime_when_timestamps_differ_in_kind() {
+        let dir = temp_dir("mixed");
+        // 途中で -Xlog の decorator から time を外した: 壁時計時刻と経過秒は比べられない
+        fs::write(dir.join("gc.log.1"), sample_lines(1, 61)).unwrap();
+        fs::write(dir.join("gc.log.0"), uptime_only(&sample_lines(62, 116))).unwrap();
+        fs::write(dir.join("gc.log"), uptime_only(&sample_lines(117, 172))).unwrap();
+
+        let paths = [
+            dir.join("gc.log"),
+            dir.join("gc.log.0"),
+            dir.join("gc.log.1"),
+        ];
+        let ordered = order_segments(paths.to_vec()).unwrap();
+        assert_eq!(file_names(&ordered), ["gc.log.1", "gc.log.0", "gc.log"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn orders_wrapped_rotation_by_uptime() {
+        let dir = temp_dir("wrapped");
+        // filecount=3 のローテーションが一周し、gc.log.0 が上書きされて最新になった
+        fs::write(dir.join("gc.log.1"), uptime_only(&sample_lines(1, 61))).unwrap();
+        fs::write(dir.join("gc.log.2"), uptime_only(&sample_lines(62, 116))).unwrap();
+        fs::write(dir.join("gc.log.0"), uptime_only(&sample_lines(117, 1000))).unwrap();
+        fs::write(dir.join("gc.log"), uptime_only(&sample_lines(1001, 1310))).unwrap();
+
+        let paths = [
+            dir.join("gc.log.0"),
+            dir.join("gc.log.1"),
+            dir.join("gc.log.2"),
+            dir.join("gc.log"),
+        ];
+        let ordered = order_segments(paths.to_vec()).unwrap();
+        assert_eq!(
+            file_names(&ordered),
+            ["gc.log.1", "gc.log.2", "gc.log.0", "gc.log"]
+        );
+        let ids = gc_ids(&paths);
+        let whole = parse_gc_events(SAMPLE.lines().map(String::from)).unwrap();
+        assert_eq!(ids.len(), whole.events.len());
+        assert!(ids.windows(2).all(|w| w[0] <= w[1]));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn breaks_uptime_ties_with_rotation_index() {
+        let dir = temp_dir("ties");
+        // 先頭の経過秒が同じセグメントはローテーション番号の順
+        fs::write(dir.join("gc.log.1"), uptime_only(&sample_lines(62, 62))).unwrap();
+        fs::write(dir.join("gc.log.0"), uptime_only(&sample_lines(62, 116))).unwrap();
+        fs::write(dir.join("gc.log"), uptime_only(&sample_lines(62, 62))).unwrap();
+
+        let paths = [
+            dir.join("gc.log"),
+            dir.join("gc.log.1"),
+            dir.join("gc.log.0"),
+        ];
+        let ordered = order_segments(paths.to_vec()).unwrap();
+        assert_eq!(file_names(&ordered), ["gc.log.0", "gc.log.1", "gc.log"]);
+
+        // ローテーションでなければ経過秒で並べる
+        fs::write(dir.join("b.log"), uptime_only(&sample_lines(62, 116))).unwrap();
+        fs::write(dir.join("a.log"), uptime_only(&sample_lines(1, 61))).unwrap();
+        let paths = [dir.join("b.log"), dir.join("a.log")];
+        assert_eq!(gc_ids(&paths), [Some(0), Some(1)]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parses_rotation_index() {
+        let index = |name: &str| rotation_index(Path::new(name));
+        assert_eq!(index("gc.log.3"), Some(("gc.log".to_string(), Some(3))));
+        assert_eq!(
+            index("gc.log.12.gz"),
+            Some(("gc.log".to_string(), Some(12)))
+        );
+        assert_eq!(
+            index("gc.log.2.current"),
+            Some(("gc.log".to_string(), Some(2)))
+        );
+        assert_eq!(index("gc.log"), Some(("gc.log".to_string(), None)));
+        assert_eq!(index(STDIN_PATH), None);
+    }
+}
@@ -0,0 +1,651 @@
+use crate::model::{
+    ConcurrentCycle, GCEvent, GcLog, GcPhase, GcType, JvmExitSummary, WorkerStats,
+    detect_evacuation_failure, detect_gc_cause, detect_humongous_object_size, detect_young_phase,
+    push_phase,
+};
+use crate::util::metaspace::apply_metaspace_summary;
+use crate::util::parser::{parse_gc_time, parse_size};
+use crate::util::reference::{apply_legacy_references, strip_legacy_references};
+use crate::util::tenuring::{apply_tenuring_line, is_tenuring_line};
+use regex::Regex;
+use std::sync::LazyLock;
+
+// JDK 8 (-XX:+PrintGCDetails) 形式のパターン
+// "2025-04-11T09:50:19.433+0900: 2.127: [GC pause (G1 Evacuation Pause) (young), 0.0214250 secs]"
+// -XX:+PrintGCDateStamps / -XX:+PrintGCTimeStamps のどちらか片方だけの場合や、
+// どちらも無く "[GC (Allocation Failure) ..." から始まる場合もある
+static RE_RECORD_START: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{3}[+-]\d{4}): )?(?:(\d+\.\d+): )?\[")
+        .unwrap()
+});
+// 並行フェーズの終了 "[GC concurrent-mark-end, 0.0033340 secs]"
+// CMS は "[CMS-concurrent-mark: 0.012/0.012 secs]" (CPU時間/経過時間)
+static RE_G1_CONCURRENT_PHASE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[GC concurrent-([\w-]+)-end, (\d+\.\d+) secs\]").unwrap());
+static RE_CMS_CONCURRENT_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\[CMS-concurrent-([\w-]+): \d+\.\d+/(\d+\.\d+) secs\]").unwrap()
+});
+// G1 の複数行ブロック内のフェーズ (インデント3文字ごとに入れ子になる)
+// "   [Parallel Time: 19.4 ms, GC Workers: 4]" / "      [Choose CSet: 0.0 ms]"
+static RE_PAUSE_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^( +)\[([^\[\]:]+?): (\d+\.\d+) ms(?:, GC Workers: (\d+))?\]$").unwrap()
+});
+// "      [Ext Root Scanning (ms): Min: 0.9, Avg: 3.9, Max: 10.4, Diff: 9.5, Sum: 15.5]"
+// (GC Worker Start / End は時刻なので Sum が無く対象外)
+static RE_WORKER_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^( +)\[(.+?) \(ms\): Min: ([\d.]+), Avg: ([\d.]+), Max: ([\d.]+), Diff: ([\d.]+), Sum: ([\d.]+)\]",
+    )
+    .unwrap()
+});
+static RE_SECS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+\.\d+) secs\]").unwrap());
+static RE_TIMES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[Times: [^\]]*\]").unwrap());
+// "[Times: user=0.08 sys=0.01, real=0.02 secs]"
+static RE_CPU_TIME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[Times: user=(\d+\.\d+) sys=(\d+\.\d+), real=(\d+\.\d+) secs\]").unwrap()
+});
+// "[ParNew: 139776K->17472K(157248K), 0.0412340 secs]" のような世代ごとの内訳
+// (失敗時は "[ParNew (promotion failed): ..." / "[CMS (concurrent mode failure): ...")
+static RE_GENERATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\[(ParNew|DefNew|PSYoungGen|ParOldGen|PSOldGen|CMS|Tenured)(?: \([a-z ]+\))?: (\d+)([KMG])->(\d+)([KMG])\((\d+)([KMG])\)",
+    )
+    .unwrap()
+});
+// CMS の concurrent mode failure では、中断された並行フェーズの終了が GC の行に割り込む
+// "[CMS2025-04-11T09:50:22.640+0900: 5.340: [CMS-concurrent-mark: 0.055/0.558 secs] [Times: ...]\n (concurrent mode failure): ..."
+static RE_CMS_INTERLEAVED_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\[CMS(?:\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{3}[+-]\d{4}: )?(?:\d+\.\d+: )?\[CMS-concurrent-([\w-]+): \d+\.\d+/(\d+\.\d+) secs\](?: ?\[Times: [^\]]*\])?\s*",
+    )
+    .unwrap()
+});
+// 世代・Metaspace の内訳を除いた後に残るヒープ全体の使用量
+static RE_NAMED_SECTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[\w ()-]+: [^\[\]]*\]").unwrap());
+static RE_HEAP: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)([KMG])->(\d+)([KMG])\((\d+)([KMG])\)").unwrap());
+// CMS の Initial Mark / Final Remark は旧世代とヒープ全体の使用量だけが出力される
+// "[1 CMS-initial-mark: 7912K(349568K)] 26280K(506816K), 0.0012340 secs]"
+static RE_CMS_MARK_HEAP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\[1 CMS-(?:initial-mark|remark): (\d+)([KMG])\((\d+)([KMG])\)\] (\d+)([KMG])\((\d+)([KMG])\)",
+    )
+    .unwrap()
+});
+// G1 の複数行ブロック内のサマリ
+// "[Eden: 300.0M(300.0M)->0.0B(288.0M) Survivors: 0.0B->16.0M Heap: 307.7M(6144.0M)->17.9M(6144.0M)]"
+static RE_G1_SUMMARY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\[Eden: ([\d.]+)([BKMG])\([\d.]+[BKMG]\)->([\d.]+)([BKMG])\(([\d.]+)([BKMG])\) Survivors: ([\d.]+)([BKMG])->([\d.]+)([BKMG]) Heap: ([\d.]+)([BKMG])\([\d.]+[BKMG]\)->([\d.]+)([BKMG])\(([\d.]+)([BKMG])\)\]",
+    )
+    .unwrap()
+});
+
+// JDK 8 のGCログを解析し、統合ロギング形式と同じ GCEvent の列を返す
+pub fn parse_legacy_gc_events<I>(lines: I) -> Result<GcLog, anyhow::Error>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parser = LegacyGcParser::default();
+    let mut gc_log = GcLog::default();
+    for log in lines {
+        gc_log.extend(parser.feed(log));
+    }
+    gc_log.extend(parser.finish());
+    Ok(gc_log)
+}
+
+// 1イベントは先頭行 (タイムスタンプ付き) と、それに続くインデントされた行で構成される
+// 次の先頭行を読んだ時点で前のイベントが確定する
+#[derive(Debug, Default)]
+pub struct LegacyGcParser {
+    record: Vec<String>,
+    concurrent: Option<ConcurrentCycle>,
+    // ログ末尾の "Heap" ブロック (JVM 終了時のヒープの状態)
+    exit: Option<JvmExitSummary>,
+    // -XX:+PrintTenuringDistribution の行が GC の行の途中に割り込んだ
+    tenuring_split: bool,
+    // タイムスタンプの無いイベントに振る通し番号 (GC ID の軸で描画するため)
+    sequence: u64,
+}
+
+impl LegacyGcParser {
+    // 1行を読み込み、確定したイベントと並行サイクルを返す
+    pub fn feed(&mut self, log: String) -> GcLog {
+        let mut out = GcLog::default();
+        // "[GC pause (G1 Evacuation Pause) (young)" の後にしきい値と年齢ごとの行が割り込み、
+        // ", 0.0214250 secs]" / ": 19136K->2112K(19136K), ..." のように続きが出力されるので先頭行に繋げる
+        let record_start = is_record_start(&log);
+        if !self.record.is_empty() && is_tenuring_line(&log) {
+            self.record.push(log);
+            self.tenuring_split = true;
+            return out;
+        }
+        if std::mem::take(&mut self.tenuring_split) && !record_start {
+            self.record[0].push_str(&log);
+            return out;
+        }
+        if !self.record.is_empty() && log.starts_with(" (") {
+            // " (concurrent mode failure): 336802K->..." は前の行の続き
+            self.record[0].push_str(&log);
+        } else if record_start {
+            flush_record(&self.record, &mut self.concurrent, &mut out);
+            self.record.clear();
+            self.record.push(log);
+        } else if !self.record.is_empty() && log.starts_with(char::is_whitespace) {
+            self.record.push(log);
+        } else if log.trim_end() == "Heap" {
+            flush_record(&self.record, &mut self.concurrent, &mut out);
+            self.record.clear();
+            self.exit = Some(JvmExitSummary::default());
+        } else if let Some(exit) = self.exit.as_mut()
+            && log.starts_with(char::is_whitespace)
+        {
+            exit.apply_line(&log);
+        } else {
+            // "CommandLine flags:" などのヘッダ行
+            flush_record(&self.record, &mut self.concurrent, &mut out);
+            self.record.clear();
+        }
+        self.number_unstamped(out)
+    }
+
+    // 追記が途切れたときに、書き終わっているイベントを確定させる (--follow で使う)
+    pub fn flush(&mut self) -> GcLog {
+        let mut out = GcLog::default();
+        if self.is_record_complete() {
+            flush_record(&self.record, &mut self.concurrent, &mut out);
+            self.record.clear();
+        }
+        self.number_unstamped(out)
+    }
+
+    // -XX:+PrintGCDetails だけのログは時刻が取れないので、出力順に GC ID を振る
+    fn number_unstamped(&mut self, mut out: GcLog) -> GcLog {
+        for event in out
+            .events
+            .iter_mut()
+            .filter(|e| e.time.is_none() && e.uptime_secs.is_none())
+        {
+            event.gc_id = Some(self.sequence);
+            self.sequence += 1;
+        }
+        out
+    }
+
+    // "[Times: ...]" まで出力されて括弧が閉じていれば、続きの行はもう無い
+    // concurrent mode failure のように別の行が割り込んだ行は括弧が閉じていないので待つ
+    fn is_record_complete(&self) -> bool {
+        let Some(last) = self.record.last() else {
+            return false;
+        };
+        let depth: i64 = self
+            .record
+            .iter()
+            .flat_map(|line| line.chars())
+            .map(|c| match c {
+                '[' => 1,
+                ']' => -1,
+                _ => 0,
+            })
+            .sum();
+        // 並行フェーズの開始・終了の行は1行で完結する
+        let single_line = self.record.len() == 1 && last.contains("concurrent-");
+        !self.tenuring_split && depth == 0 && (last.contains("[Times:") || single_line)
+    }
+
+    // ログ末尾の読みかけのイベントを確定させる
+    pub fn finish(mut self) -> GcLog {
+        let mut out = GcLog::default();
+        flush_record(&self.record, &mut self.concurrent, &mut out);
+        out.exit = self.exit.take();
+        self.number_unstamped(out)
+    }
+}
+
+// 行頭に日時・JVM起動からの経過秒の少なくとも一方があるか、
+// タイムスタンプ無しの "[GC" / "[Full GC" で始まればイベントの先頭行
+fn is_record_start(line: &str) -> bool {
+    line.starts_with("[GC")
+        || line.starts_with("[Full GC")
+        || RE_RECORD_START
+            .captures(line)
+            .is_some_and(|caps| caps.get(1).is_some() || caps.get(2).is_some())
+}
+
+fn flush_record(record: &[String], concurrent: &mut Option<ConcurrentCycle>, out: &mut GcLog) {
+    let Some(header) = record.first() else {
+        return;
+    };
+    let header = &*strip_legacy_references(header);
+    let interleaved = RE_CMS_INTERLEAVED_PHASE
+        .captures(header)
+        .map(|caps| GcPhase::new(&caps[1], caps[2].parse::<f64>().unwrap_or(0.0) * 1000.0));
+    let header = &*RE_CMS_INTERLEAVED_PHASE.replace_all(header, "[CMS ");
+    let Some(start) = RE_RECORD_START.captures(header) else {
+        return;
+    };
+    let time = start.get(1).and_then(|m| parse_gc_time(m.as_str()));
+    let uptime_secs = start.get(2).and_then(|m| m.as_str().parse::<f64>().ok());
+    // タイムスタンプの直後の "[GC concurrent-..." 等
+    let body = &header[start.get(0).unwrap().end() - 1..];
+
+    // 並行サイクルは開始から終了 (cleanup-end / CMS-concurrent-reset) までを1つの ConcurrentCycle にする
+    if header.contains("concurrent-root-region-scan-start")
+        || header.contains("CMS-concurrent-mark-start")
+    {
+        *concurrent = Some(ConcurrentCycle {
+            start: time,
+            start_uptime_secs: uptime_secs,
+            ..Default::default()
+        });
+        return;
+    }
+    let phase = RE_G1_CONCURRENT_PHASE
+        .captures(body)
+        .or_else(|| RE_CMS_CONCURRENT_PHASE.captures(body));
+    if let Some(caps) = &phase
+        && let Some(cycle) = concurrent.as_mut()
+    {
+        let duration_ms = caps[2].parse::<f64>().unwrap_or(0.0) * 1000.0;
+        cycle.phases.push(GcPhase::new(&caps[1], duration_ms));
+    }
+    let mode_failure = header.contains("concurrent mode failure");
+    if let Some(phase) = interleaved
+        && let Some(cycle) = concurrent.as_mut()
+    {
+        cycle.phases.push(phase);
+    }
+    let aborted = header.contains("concurrent-mark-abort") || mode_failure;
+    let finished = aborted
+        || header.contains("concurrent-cleanup-end")
+        || header.contains("CMS-concurrent-reset:");
+    if finished && let Some(mut cycle) = concurrent.take() {
+        cycle.end = time;
+        cycle.end_uptime_secs = uptime_secs;
+        cycle.aborted = aborted;
+        cycle.duration_ms = cycle
+            .start_uptime_secs
+            .zip(uptime_secs)
+            .map(|(start, end)| (end - start) * 1000.0);
+        out.cycles.push(cycle);
+    }
+    // CMS の concurrent mode failure は Full GC として続けて記録する
+    if phase.is_some()
+        || (body.contains("concurrent") && !header.contains("[Full GC") && !mode_failure)
+    {
+        return;
+    }
+
+    let gc_type = if header.contains("[Full GC") || mode_failure {
+        GcType::Full
+    } else if header.contains("[GC pause") && header.contains("(mixed)") {
+        GcType::Mixed
+    } else if header.contains("[GC pause") {
+        GcType::Young
+    } else if header.contains("[GC remark") || header.contains("[GC (CMS Final Remark)") {
+        GcType::Remark
+    } else if header.contains("[GC (CMS Initial Mark)") {
+        GcType::InitialMark
+    } else if header.contains("[GC cleanup") {
+        GcType::Cleanup
+    } else if RE_GENERATION
+        .captures(header)
+        .is_some_and(|caps| matches!(&caps[1], "ParNew" | "DefNew" | "PSYoungGen"))
+    {
+        GcType::Young
+    } else {
+        return;
+    };
+
+    // CMS / Parallel は "[Times: ... real=0.04 secs]" が同じ行に続く
+    let header = RE_TIMES.replace_all(header, "");
+    let mut event = GCEvent {
+        time,
+        uptime_secs,
+        gc_type,
+        gc_cause: detect_gc_cause(&header),
+        young_phase: detect_young_phase(&header),
+        evacuation_failure: record
+            .iter()
+            .filter_map(|line| detect_evacuation_failure(line))
+            .max(),
+        humongous_object_sizes: record
+            .iter()
+            .filter_map(|line| detect_humongous_object_size(line))
+            .collect(),
+        has_pause: true,
+        // 入れ子の内訳より後ろに出る、最後の所要時間がPause全体の時間
+        pause_time_ms: RE_SECS
+            .captures_iter(&header)
+            .last()
+            .and_then(|caps| caps[1].parse::<f64>().ok())
+            .map(|secs| secs * 1000.0),
+        ..Default::default()
+    };
+    for line in record {
+        apply_tenuring_line(&mut event, line);
+        apply_legacy_references(&mut event, line);
+    }
+
+    for caps in RE_GENERATION.captures_iter(&header) {
+        let before = parse_size(&caps[2], &caps[3]);
+        let after = parse_size(&caps[4], &caps[5]);
+        let total = parse_size(&caps[6], &caps[7]);
+        match &caps[1] {
+            "ParNew" | "DefNew" | "PSYoungGen" => {
+                event.young_gen_before = before;
+                event.young_gen_after = after;
+                event.young_gen_total = total;
+            }
+            _ => {
+                event.old_gen_before = before;
+                event.old_gen_after = after;
+                event.old_gen_total = total;
+            }
+        }
+    }
+
+    let stripped = RE_NAMED_SECTION.replace_all(&header, "");
+    if let Some(caps) = RE_HEAP.captures(&stripped) {
+        event.heap_used_before = parse_size(&caps[1], &caps[2]);
+        event.heap_used_after = parse_size(&caps[3], &caps[4]);
+        event.heap_committed = parse_size(&caps[5], &caps[6]);
+    } else if let Some(caps) = RE_CMS_MARK_HEAP.captures(&header) {
+        // マーキングの Pause では回収しないので、前後の使用量は同じ
+        let old = parse_size(&caps[1], &caps[2]);
+        let heap = parse_size(&caps[5], &caps[6]);
+        event.old_gen_before = old;
+        event.old_gen_after = old;
+        event.old_gen_total = parse_size(&caps[3], &caps[4]);
+        event.heap_used_before = heap;
+        event.heap_used_after = heap;
+        event.heap_committed = parse_size(&caps[7], &caps[8]);
+    }
+
+    let mut gc_workers = None;
+    for line in &record[1..] {
+        let phase = if let Some(caps) = RE_PAUSE_PHASE.captures(line) {
+            if let Some(workers) = caps.get(4) {
+                gc_workers = workers.as_str().parse().ok();
+            }
+            (
+                caps[1].len(),
+                GcPhase::new(&caps[2], caps[3].parse().unwrap_or(0.0)),
+            )
+        } else if let Some(caps) = RE_WORKER_PHASE.captures(line) {
+            let workers = WorkerStats {
+                min: caps[3].parse().unwrap_or(0.0),
+                avg: caps[4].parse().unwrap_or(0.0),
+                max: caps[5].parse().unwrap_or(0.0),
+                diff: caps[6].parse().unwrap_or(0.0),
+                sum: caps[7].parse().unwrap_or(0.0),
+                workers: gc_workers,
+            };
+            (caps[1].len(), GcPhase::with_workers(&caps[2], workers))
+        } else {
+            continue;
+        };
+        push_phase(&mut event.phases, (phase.0 / 3).saturating_sub(1), phase.1);
+    }
+
+    // G1 は Eden / Survivors / Heap のサイズから世代ごとの使用量を求める
+    let body = record.join("\n");
+    if let Some(caps) = RE_G1_SUMMARY.captures(&body) {
+        let eden_before = parse_size(&caps[1], &caps[2]).unwrap_or(0.0);
+        let eden_after = parse_size(&caps[3], &caps[4]).unwrap_or(0.0);
+        let eden_total = parse_size(&caps[5], &caps[6]).unwrap_or(0.0);
+        let survivor_before = parse_size(&caps[7], &caps[8]).unwrap_or(0.0);
+        let survivor_after = parse_size(&caps[9], &caps[10]).unwrap_or(0.0);
+        let heap_before = parse_size(&caps[11], &caps[12]).unwrap_or(0.0);
+        let heap_after = parse_size(&caps[13], &caps[14]).unwrap_or(0.0);
+
+        event.heap_used_before = Some(heap_before);
+        event.heap_used_after = Some(heap_after);
+        event.heap_committed = parse_size(&caps[15], &caps[16]);
+        event.young_gen_before = Some(eden_before + survivor_before);
+        event.young_gen_after = Some(eden_after + survivor_after);
+        event.young_gen_total = Some(eden_total + survivor_after);
+        event.old_gen_before = Some(heap_before - eden_before - survivor_before);
+        event.old_gen_after = Some(heap_after - eden_after - survivor_after);
+    }
+    apply_metaspace_summary(&mut event, &body);
+    if let Some(caps) = RE_CPU_TIME.captures_iter(&body).last() {
+        event.set_cpu_times(
+            caps[1].parse().unwrap_or(0.0),
+            caps[2].parse().unwrap_or(0.0),
+            caps[3].parse().unwrap_or(0.0),
+        );
+    }
+
+    // ParNew 等は旧世代の内訳が出ないので、ヒープ全体との差分で補う
+    if event.old_gen_before.is_none() {
+        if let (Some(heap), Some(young)) = (event.heap_used_before, event.young_gen_before) {
+            event.old_gen_before = Some(heap - young);
+        }
+        if let (Some(heap), Some(young)) = (event.heap_used_after, event.young_gen_after) {
+            event.old_gen_after = Some(heap - young);
+        }
+    }
+    // concurrent mode failure の ParNew は失敗した Young GC の値なので、Full GC 後の若い世代は差分で求める
+    if mode_failure && let (Some(heap), Some(old)) = (event.heap_used_after, event.old_gen_after) {
+        event.young_gen_after = Some(heap - old);
+    }
+
+    out.events.push(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{GcCause, GcType};
+    use crate::util::units::MB;
+
+    const KB: f64 = 1024.0;
+
+    fn parse(text: &str) -> GcLog {
+        parse_legacy_gc_events(text.lines().map(String::from)).unwrap()
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn parses_g1_sample() {
+        let events = parse(include_str!("../../sample/sample_gc_jdk8.log")).events;
+        assert_eq!(events.len(), 8);
+        let first = &events[0];
+        assert_eq!(first.gc_type, GcType::Young);
+        assert_eq!(first.gc_cause, Some(GcCause::EvacuationPause));
+        assert_close(first.uptime_secs, 2.127);
+        assert_close(first.pause_time_ms, 21.425);
+        assert_close(first.heap_used_before, 307.7 * MB);
+        assert_close(first.heap_used_after, 17.9 * MB);
+        assert_close(first.heap_committed, 6144.0 * MB);
+    }
+
+    #[test]
+    fn records_cms_initial_mark_and_concurrent_mode_failure() {
+        let log = parse(include_str!("../../sample/sample_cms_jdk8.log"));
+        let count = |gc_type: GcType| log.events.iter().filter(|e| e.gc_type == gc_type).count();
+        assert_eq!(count(GcType::Young), 7);
+        assert_eq!(count(GcType::InitialMark), 2);
+        assert_eq!(count(GcType::Remark), 1);
+        assert_eq!(count(GcType::Full), 1);
+
+        // [GC (CMS Initial Mark) [1 CMS-initial-mark: 183874K(349568K)] 203218K(506816K), 0.0034120 secs]
+        let initial_mark = log
+            .events
+            .iter()
+            .find(|e| e.gc_type == GcType::InitialMark)
+            .unwrap();
+        assert_eq!(initial_mark.gc_cause, Some(GcCause::CmsInitialMark));
+        assert!(initial_mark.has_pause);
+        assert_close(initial_mark.uptime_secs, 3.072);
+        assert_close(initial_mark.pause_time_ms, 3.412);
+        assert_close(initial_mark.heap_used_before, 203218.0 * KB);
+        assert_close(initial_mark.heap_used_after, 203218.0 * KB);
+        assert_close(initial_mark.heap_committed, 506816.0 * KB);
+        assert_close(initial_mark.old_gen_after, 183874.0 * KB);
+
+        // 2行に分かれた concurrent mode failure は Full GC として記録し、並行サイクルは中断扱い
+        let full = log
+            .events
+            .iter()
+            .find(|e| e.gc_type == GcType::Full)
+            .unwrap();
+        assert_close(full.heap_used_before, 494050.0 * KB);
+        assert_close(full.heap_used_after, 151023.0 * KB);
+        assert_close(full.old_gen_after, 151023.0 * KB);
+        assert_eq!(log.cycles.len(), 2);
+        assert_eq!(log.cycles.iter().filter(|c| c.aborted).count(), 1);
+    }
+
+    #[test]
+    fn parses_date_stamps_without_uptime() {
+        let events = parse(include_str!("../../sample/sample_parallel_jdk8.log")).events;
+        assert_eq!(events.len(), 9);
+        assert_eq!(
+            events.iter().filter(|e| e.gc_type == GcType::Full).count(),
+            3
+        );
+        assert!(
+            events
+                .iter()
+                .all(|e| e.time.is_some() && e.uptime_secs.is_none())
+        );
+
+        // Full GC の世代ごとのサイズの合計はヒープ全体の値になる
+        for full in events.iter().filter(|e| e.gc_type == GcType::Full) {
+            let sum = |young: Option<f64>, old: Option<f64>| young.unwrap() + old.unwrap();
+            assert_close(
+                full.heap_used_before,
+                sum(full.young_gen_before, full.old_gen_before),
+            );
+            assert_close(
+                full.heap_used_after,
+                sum(full.young_gen_after, full.old_gen_after),
+            );
+            assert_close(
+                full.heap_committed,
+                sum(full.young_gen_total, full.old_gen_total),
+            );
+            assert!(full.old_gen_before.unwrap() <= full.old_gen_total.unwrap());
+        }
+
+        let last = events.last().unwrap();
+        assert_eq!(last.gc_type, GcType::Full);
+        assert_eq!(last.gc_cause, Some(GcCause::SystemGc));
+        assert_close(last.pause_time_ms, 198.765);
+        assert_close(last.heap_used_before, 136607.0 * KB);
+        assert_close(last.heap_used_after, 98765.0 * KB);
+    }
+
+    #[test]
+    fn flushes_completed_records_on_idle() {
+        // 1行ごとに追記が途切れても、一括で解析したときと同じイベントになる
+        for text in [
+            include_str!("../../sample/sample_gc_jdk8.log"),
+            include_str!("../../sample/sample_cms_jdk8.log"),
+            include_str!("../../sample/sample_parallel_jdk8.log"),
+        ] {
+            let mut parser = LegacyGcParser::default();
+            let mut log = GcLog::default();
+            for line in text.lines() {
+                log.extend(parser.feed(line.to_string()));
+                log.extend(parser.flush());
+            }
+            log.extend(parser.finish());
+            let expected = parse(text);
+            let pauses = |log: &GcLog| -> Vec<(GcType, Option<f64>)> {
+                log.events
+                    .iter()
+                    .map(|e| (e.gc_type.clone(), e.pause_time_ms))
+                    .collect()
+            };
+            assert_eq!(pauses(&log), pauses(&expected));
+            assert_eq!(log.cycles.len(), expected.cycles.len());
+        }
+
+        // 最後の GC は次の GC を待たずに確定する
+        let mut parser = LegacyGcParser::default();
+        let text = include_str!("../../sample/sample_parallel_jdk8.log");
+        let lines: Vec<&str> = text.lines().take_while(|l| *l != "Heap").collect();
+        for line in &lines {
+            parser.feed(line.to_string());
+        }
+        let last = parser.flush().events;
+        assert_eq!(last.len(), 1);
+        assert_close(last[0].pause_time_ms, 198.765);
+        assert!(parser.flush().events.is_empty());
+
+        // concurrent mode failure の続きの行が出力されるまでは確定しない
+        let mut parser = LegacyGcParser::default();
+        let text = include_str!("../../sample/sample_cms_jdk8.log");
+        let (head, rest) = text.split_at(text.find(" (concurrent mode failure)").unwrap());
+        for line in head.lines() {
+            parser.feed(line.to_string());
+        }
+        assert!(parser.flush().events.is_empty());
+        parser.feed(rest.lines().next().unwrap().to_string());
+        let full = parser.flush().events;
+        assert_eq!(full.len(), 1);
+        assert_eq!(full[0].gc_type, GcType::Full);
+    }
+
+    #[test]
+    fn detects_record_start_with_either_stamp() {
+        assert!(is_record_start(
+            "2025-04-11T09:50:19.433+0900: 2.127: [GC pause (G1 Evacuation Pause) (young), 0.0214250 secs]"
+        ));
+        assert!(is_record_start(
+            "2.127: [GC (Allocation Failure) [PSYoungGen: 65536K->10720K(76288K)] 65536K->10728K(251392K), 0.0083450 secs]"
+        ));
+        assert!(is_record_start(
+            "2025-04-11T09:50:21.912+0900: [Full GC (System.gc()) [PSYoungGen: 4096K->0K(141824K)]"
+        ));
+        assert!(!is_record_start(
+            "   [Eden: 304.0M(300.0M)->0.0B(288.0M) Survivors: 0.0B->16.0M Heap: 307.7M(6144.0M)->17.9M(6144.0M)]"
+        ));
+        assert!(is_record_start(
+            "[GC (Allocation Failure) [PSYoungGen: 65536K->10720K(76288K)] 65536K->10728K(251392K), 0.0083450 secs]"
+        ));
+        assert!(is_record_start(
+            "[Full GC (Ergonomics) [PSYoungGen: 17392K->0K(157184K)] [ParOldGen: 77085K->79121K(349696K)]"
+        ));
+        assert!(!is_record_start("CommandLine flags: -XX:+UseParallelGC"));
+    }
+
+    #[test]
+    fn parses_records_without_stamps() {
+        // -XX:+PrintGCDetails だけのログはタイムスタンプが無い
+        let stamped = parse(include_str!("../../sample/sample_parallel_jdk8.log")).events;
+        let log = parse(include_str!(
+            "../../sample/sample_parallel_jdk8_nostamps.log"
+        ));
+        assert_eq!(log.events.len(), stamped.len());
+        assert!(
+            log.events
+                .iter()
+                .all(|e| e.time.is_none() && e.uptime_secs.is_none())
+        );
+        let ids: Vec<Option<u64>> = log.events.iter().map(|e| e.gc_id).collect();
+        assert_eq!(ids, (0..stamped.len() as u64).map(Some).collect::<Vec<_>>());
+        for (event, expected) in log.events.iter().zip(&stamped) {
+            assert_eq!(event.gc_type, expected.gc_type);
+            assert_eq!(event.gc_cause, expected.gc_cause);
+            assert_eq!(event.pause_time_ms, expected.pause_time_ms);
+            assert_eq!(event.heap_used_after, expected.heap_used_after);
+            assert_eq!(event.old_gen_after, expected.old_gen_after);
+        }
+        assert!(log.exit.is_some());
+    }
+}
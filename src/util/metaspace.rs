@@ -0,0 +1,196 @@
+use crate::model::GCEvent;
+use crate::util::parser::parse_size;
+use regex::Regex;
+use std::sync::LazyLock;
+
+// [gc,metaspace] のサマリ行
+// JDK 11-16 / JDK 8: "Metaspace: 15646K->15646K(1064960K)" (括弧内は reserved)
+static RE_METASPACE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Metaspace: (\d+)([KMG])->(\d+)([KMG])\((\d+)([KMG])\)").unwrap());
+// JDK 17+: "Metaspace: 15646K(15872K)->15646K(15872K) NonClass: ... Class: 1711K(1792K)->1711K(1792K)" (括弧内は committed)
+static RE_METASPACE_COMMITTED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Metaspace: (\d+)([KMG])\((\d+)([KMG])\)->(\d+)([KMG])\((\d+)([KMG])\)").unwrap()
+});
+static RE_CLASS_SPACE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r" Class: (\d+)([KMG])\((\d+)([KMG])\)->(\d+)([KMG])\((\d+)([KMG])\)").unwrap()
+});
+// ZGC: "Metaspace: 20M used, 21M committed, 1088M reserved" (GC後の値のみ)
+static RE_METASPACE_ZGC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Metaspace: (\d+)([KMG]) used, (\d+)([KMG]) committed, (\d+)([KMG]) reserved")
+        .unwrap()
+});
+// debug の Heap before/after GC ブロックと、JVM 終了時の Heap ブロック内の行
+// "GC(1)  Metaspace       used 15729K, capacity 16408K, committed 16768K, reserved 1064960K"
+// "GC(1)   class space    used 1624K, capacity 1885K, committed 1920K, reserved 1048576K"
+// (JDK 17+ は capacity が無い)
+static RE_METASPACE_DETAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:^|\s)(Metaspace|class space) +used (\d+)K, (?:capacity \d+K, )?committed (\d+)K, reserved (\d+)K",
+    )
+    .unwrap()
+});
+
+// Heap ブロック内の Metaspace / class space の行 (bytes)
+#[derive(Debug, Clone, Copy)]
+pub struct MetaspaceDetail {
+    pub class_space: bool,
+    pub used: Option<f64>,
+    pub committed: Option<f64>,
+    pub reserved: Option<f64>,
+}
+
+pub fn parse_metaspace_detail(line: &str) -> Option<MetaspaceDetail> {
+    let caps = RE_METASPACE_DETAIL.captures(line)?;
+    Some(MetaspaceDetail {
+        class_space: &caps[1] == "class space",
+        used: parse_size(&caps[2], "K"),
+        committed: parse_size(&caps[3], "K"),
+        reserved: parse_size(&caps[4], "K"),
+    })
+}
+
+// Metaspace / Compressed Class Space の使用量をイベントに反映する
+// after_gc は Heap after GC ブロックの中の行かどうか
+pub fn apply_metaspace_line(event: &mut GCEvent, line: &str, after_gc: bool) {
+    if let Some(detail) = parse_metaspace_detail(line) {
+        match (detail.class_space, after_gc) {
+            (false, false) => event.metaspace_used_before = detail.used,
+            (false, true) => {
+                event.metaspace_used_after = detail.used;
+                event.metaspace_committed = detail.committed;
+                event.metaspace_reserved = detail.reserved;
+            }
+            (true, false) => event.class_space_used_before = detail.used,
+            (true, true) => {
+                event.class_space_used_after = detail.used;
+                event.class_space_committed = detail.committed;
+            }
+        }
+        return;
+    }
+    apply_metaspace_summary(event, line);
+}
+
+// サマリ行 (JDK 8 は GC の行の中の "[Metaspace: ...]")
+pub fn apply_metaspace_summary(event: &mut GCEvent, text: &str) {
+    if let Some(caps) = RE_METASPACE_COMMITTED.captures(text) {
+        event.metaspace_used_before = parse_size(&caps[1], &caps[2]);
+        event.metaspace_used_after = parse_size(&caps[5], &caps[6]);
+        event.metaspace_committed = parse_size(&caps[7], &caps[8]);
+        if let Some(caps) = RE_CLASS_SPACE.captures(text) {
+            event.class_space_used_before = parse_size(&caps[1], &caps[2]);
+            event.class_space_used_after = parse_size(&caps[5], &caps[6]);
+            event.class_space_committed = parse_size(&caps[7], &caps[8]);
+        }
+    } else if let Some(caps) = RE_METASPACE.captures(text) {
+        event.metaspace_used_before = parse_size(&caps[1], &caps[2]);
+        event.metaspace_used_after = parse_size(&caps[3], &caps[4]);
+        event.metaspace_reserved = parse_size(&caps[5], &caps[6]);
+    } else if let Some(caps) = RE_METASPACE_ZGC.captures(text) {
+        event.metaspace_used_after = parse_size(&caps[1], &caps[2]);
+        event.metaspace_committed = parse_size(&caps[3], &caps[4]);
+        event.metaspace_reserved = parse_size(&caps[5], &caps[6]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::util::units::MB;
+
+    const K: f64 = 1024.0;
+
+    fn apply(lines: &[(&str, bool)]) -> GCEvent {
+        let mut event = GCEvent::default();
+        for (line, after_gc) in lines {
+            apply_metaspace_line(&mut event, line, *after_gc);
+        }
+        event
+    }
+
+    #[test]
+    fn reads_heap_block_details() {
+        // sample_gc.log の GC(0) の Heap before GC ブロックと、JDK 17+ の capacity の無い after ブロック
+        let event = apply(&[
+            (
+                "[2.127s][debug][gc,heap      ] GC(0)  Metaspace       used 15646K, capacity 16310K, committed 16512K, reserved 1064960K",
+                false,
+            ),
+            (
+                "[2.127s][debug][gc,heap      ] GC(0)   class space    used 1621K, capacity 1851K, committed 1920K, reserved 1048576K",
+                false,
+            ),
+            (
+                "[2.149s][debug][gc,heap      ] GC(0)  Metaspace       used 15650K, committed 16512K, reserved 1064960K",
+                true,
+            ),
+            (
+                "[2.149s][debug][gc,heap      ] GC(0)   class space    used 1622K, committed 1920K, reserved 1048576K",
+                true,
+            ),
+        ]);
+        assert_eq!(event.metaspace_used_before, Some(15646.0 * K));
+        assert_eq!(event.metaspace_used_after, Some(15650.0 * K));
+        assert_eq!(event.metaspace_committed, Some(16512.0 * K));
+        assert_eq!(event.metaspace_reserved, Some(1064960.0 * K));
+        assert_eq!(event.class_space_used_before, Some(1621.0 * K));
+        assert_eq!(event.class_space_used_after, Some(1622.0 * K));
+        assert_eq!(event.class_space_committed, Some(1920.0 * K));
+    }
+
+    #[test]
+    fn reads_summary_lines() {
+        // JDK 11-16 (括弧内は reserved)
+        let event = apply(&[(
+            "[2.149s][info ][gc,metaspace ] GC(0) Metaspace: 15646K->15646K(1064960K)",
+            false,
+        )]);
+        assert_eq!(event.metaspace_used_before, Some(15646.0 * K));
+        assert_eq!(event.metaspace_used_after, Some(15646.0 * K));
+        assert_eq!(event.metaspace_reserved, Some(1064960.0 * K));
+        assert_eq!(event.metaspace_committed, None);
+
+        // JDK 17+ (括弧内は committed, Class は Compressed Class Space)
+        let event = apply(&[(
+            "[2.149s][info ][gc,metaspace] GC(0) Metaspace: 15646K(15872K)->15650K(15936K) NonClass: 13935K(14080K)->13938K(14144K) Class: 1711K(1792K)->1712K(1792K)",
+            false,
+        )]);
+        assert_eq!(event.metaspace_used_before, Some(15646.0 * K));
+        assert_eq!(event.metaspace_used_after, Some(15650.0 * K));
+        assert_eq!(event.metaspace_committed, Some(15936.0 * K));
+        assert_eq!(event.class_space_used_before, Some(1711.0 * K));
+        assert_eq!(event.class_space_used_after, Some(1712.0 * K));
+        assert_eq!(event.class_space_committed, Some(1792.0 * K));
+
+        // ZGC (GC後の値のみ)
+        let event = apply(&[(
+            "[15.030s][info ][gc,metaspace ] GC(5) Metaspace: 40M used, 41M committed, 1088M reserved",
+            false,
+        )]);
+        assert_eq!(event.metaspace_used_before, None);
+        assert_eq!(event.metaspace_used_after, Some(40.0 * MB));
+        assert_eq!(event.metaspace_committed, Some(41.0 * MB));
+        assert_eq!(event.metaspace_reserved, Some(1088.0 * MB));
+    }
+
+    #[test]
+    fn reads_legacy_summary_in_gc_line() {
+        // sample_cms_jdk8.log の concurrent mode failure
+        let mut event = GCEvent::default();
+        apply_metaspace_summary(
+            &mut event,
+            " (concurrent mode failure): 336802K->151023K(349568K), 0.8123400 secs] 494050K->151023K(506816K), [Metaspace: 21034K->21034K(1069056K)], 0.8124510 secs]",
+        );
+        assert_eq!(event.metaspace_used_before, Some(21034.0 * K));
+        assert_eq!(event.metaspace_used_after, Some(21034.0 * K));
+        assert_eq!(event.metaspace_reserved, Some(1069056.0 * K));
+
+        // "Purge Metaspace" のフェーズ行は対象外
+        let event = apply(&[(
+            "[3.617s][debug][gc,phases     ] GC(3) Purge Metaspace 0.000ms",
+            false,
+        )]);
+        assert_eq!(event.metaspace_used_after, None);
+    }
+}
@@ -1,5 +1,33 @@
+pub mod analyzer;
+pub mod decorator;
 pub mod drawer;
+pub mod follow;
+pub mod heap_block;
+pub mod input;
+pub mod legacy;
+pub mod metaspace;
 pub mod parser;
+pub mod reference;
+pub mod report;
+pub mod shenandoah;
+pub mod tenuring;
+pub mod units;
+pub mod wrapper;
+pub mod zgc;
 
+pub use analyzer::*;
+pub use decorator::*;
 pub use drawer::*;
+pub use follow::*;
+pub use heap_block::*;
+pub use input::*;
+pub use legacy::*;
+pub use metaspace::*;
 pub use parser::*;
+pub use reference::*;
+pub use report::*;
+pub use shenandoah::*;
+pub use tenuring::*;
+pub use units::*;
+pub use wrapper::*;
+pub use zgc::*;
@@ -1,155 +1,947 @@
-use crate::model::{GCEvent, GcType, detect_gc_type};
+use crate::model::{
+    ConcurrentCycle, GCEvent, GcCause, GcLog, GcPhase, GcType, JvmExitSummary, LogFormat, LogInfo,
+    WorkerStats, detect_evacuation_failure, detect_gc_cause, detect_gc_type,
+    detect_humongous_object_size, detect_young_phase, push_phase,
+};
+use crate::util::decorator::parse_decorations;
+use crate::util::heap_block::{apply_heap_block_line, check_heap_block, heap_block_kind};
+use crate::util::legacy::{LegacyGcParser, parse_legacy_gc_events};
+use crate::util::metaspace::apply_metaspace_line;
+use crate::util::reference::{ReferenceSection, apply_reference_line};
+use crate::util::shenandoah::{apply_shenandoah_line, detect_shenandoah_trigger};
+use crate::util::tenuring::apply_tenuring_line;
+use crate::util::units::{GB, MB};
+use crate::util::zgc::apply_zgc_line;
 use chrono::{DateTime, FixedOffset};
+use flate2::read::MultiGzDecoder;
 use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Lines};
+use std::io::{self, BufRead};
 use std::path::Path;
+use std::sync::LazyLock;
 
 // 出力はResult型にラップされ、エラーをマッチできるようになる。
 // ファイルの各行のReaderへのイテレータを返す。
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+// gzip / zstd で圧縮されたファイルは先頭のマジックナンバーから判定して透過的に展開する
+pub fn read_lines<P>(filename: P) -> io::Result<LogLines>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(LogLines::new(decompress(io::BufReader::new(File::open(
+        filename,
+    )?))?))
 }
 
+// 標準入力 ("--input -") の各行へのイテレータを返す
+pub fn read_stdin_lines() -> io::Result<LogLines> {
+    Ok(LogLines::new(decompress(io::stdin().lock())?))
+}
+
+// 行単位のイテレータ
+// 不正な UTF-8 を含む行は置換文字に置き換えて読み進め、I/O エラー (壊れた圧縮データ等) だけを返す
+pub struct LogLines {
+    reader: Box<dyn BufRead>,
+}
+
+impl LogLines {
+    pub fn new(reader: Box<dyn BufRead>) -> Self {
+        LogLines { reader }
+    }
+}
+
+impl Iterator for LogLines {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = Vec::new();
+        match self.reader.read_until(b'\n', &mut buf) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(String::from_utf8_lossy(&buf)
+                .trim_end_matches(['\r', '\n'])
+                .to_string())),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+fn decompress<R: BufRead + 'static>(mut reader: R) -> io::Result<Box<dyn BufRead>> {
+    let magic = reader.fill_buf()?;
+    let reader: Box<dyn BufRead> = if magic.starts_with(&GZIP_MAGIC) {
+        Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
+    } else if magic.starts_with(&ZSTD_MAGIC) {
+        Box::new(io::BufReader::new(zstd::Decoder::with_buffer(reader)?))
+    } else {
+        Box::new(reader)
+    };
+    Ok(reader)
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
 pub fn parse_gc_time(raw: &str) -> Option<DateTime<FixedOffset>> {
     DateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.3f%z").ok()
 }
 
-// GCログから構造体にマッピングして、データのデシリアライズを行う
-pub fn parse_gc_events(lines: Lines<BufReader<File>>) -> Result<Vec<GCEvent>, anyhow::Error> {
-    let mut events: Vec<GCEvent> = Vec::new();
-    let mut current = GCEvent {
-        time: None,
-        gc_type: GcType::Unknown,
-        has_pause: false,
-        pause_time_ms: None,
-        eden_before: None,
-        eden_after: None,
-        eden_total: None,
-        survivor_before: None,
-        survivor_after: None,
-        survivor_total: None,
-        old_before: None,
-        old_after: None,
-        humongous_before: None,
-        humongous_after: None,
+// "307" と "M" のような数値と単位の組をバイト数に変換する
+pub fn parse_size(value: &str, unit: &str) -> Option<f64> {
+    let value: f64 = value.parse().ok()?;
+    let scale = match unit {
+        "B" | "" => 1.0,
+        "K" => 1024.0,
+        "M" => MB,
+        "G" => GB,
+        "T" => GB * 1024.0,
+        _ => return None,
     };
+    Some(value * scale)
+}
 
-    // パターン条件を定義
-    let re_time = Regex::new(r"\[(.*?)\]").unwrap();
-    let re_gc = Regex::new(r"GC\(").unwrap();
-    let re_pause = Regex::new("Pause").unwrap();
-    let re_pause_time = Regex::new(r"(\d+\.\d+)ms$").unwrap();
-    let re_eden = Regex::new("Eden regions").unwrap();
-    let re_survivor = Regex::new("Survivor regions").unwrap();
-    let re_old = Regex::new("Old regions").unwrap();
-    let re_humongous = Regex::new("Humongous regions").unwrap();
-
-    // イテレータを消費し、Option型のStringを返す。
-    for line in lines {
-        if let Ok(log) = line {
-            // ここでパターンマッチングをする
-            if re_gc.is_match(&log) {
-                if let Some(caps) = re_time.captures(&log) {
-                    let time_str = &caps[1];
-                    current.time = parse_gc_time(time_str);
-                }
+// パターン条件を定義
+static RE_GC_ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"GC\((\d+)\)").unwrap());
+static RE_PAUSE: LazyLock<Regex> = LazyLock::new(|| Regex::new("Pause").unwrap());
+static RE_PAUSE_TIME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+\.\d+)ms$").unwrap());
+// "[gc] GC(0) Pause Young (Normal) (G1 Evacuation Pause) 307M->17M(6144M) 21.425ms" がPause時間の正
+static RE_PAUSE_SUMMARY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Pause .*?(\d+)([BKMG])->(\d+)([BKMG])\((\d+)([BKMG])\) (\d+\.\d+)ms$").unwrap()
+});
+// "[gc,heap,exit] Heap" に続いて "garbage-first heap   total 6291456K, used 3098663K [..." 等が出力される
+static RE_HEAP_EXIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[gc,heap,exit\s*\]").unwrap());
+static RE_HEAP_EXIT_START: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\]\s*Heap\s*$").unwrap());
+// "[gc,cpu] GC(0) User=0.08s Sys=0.01s Real=0.02s"
+static RE_CPU_TIME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"GC\(\d+\) User=(\d+\.\d+)s Sys=(\d+\.\d+)s Real=(\d+\.\d+)s").unwrap()
+});
+// G1 の [gc,phases] の内訳 (インデントで入れ子を表す)
+// "GC(1)   Evacuate Collection Set: 15.6ms" / "GC(1)     Prepare TLABs: 0.0ms"
+// Remark / Cleanup / Full GC はコロン無し: "GC(3) Finalize Marking 0.262ms" / "GC(10) Phase 1: Mark live objects 50.123ms"
+static RE_PAUSE_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"GC\(\d+\)( +)([A-Za-z][A-Za-z0-9 :/-]*?):? (\d+\.\d+)ms$").unwrap()
+});
+// "GC(1)     Ext Root Scanning (ms):   Min:  0.0, Avg:  0.4, Max:  0.8, Diff:  0.8, Sum:  1.8, Workers: 4"
+static RE_WORKER_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"GC\(\d+\)( +)(.+?) \(ms\): +Min: +([\d.]+), Avg: +([\d.]+), Max: +([\d.]+), Diff: +([\d.]+), Sum: +([\d.]+), Workers: +(\d+)",
+    )
+    .unwrap()
+});
+// G1 の並行サイクルのフェーズ "GC(5) Concurrent Scan Root Regions 9.833ms"
+// (入れ子の親である "Concurrent Mark (4.878s, 4.880s) 2.263ms" は対象外)
+static RE_CONCURRENT_PHASE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"GC\(\d+\) (Concurrent [A-Za-z ]+?) (\d+(?:\.\d+)?)ms$").unwrap());
+static RE_ZGC_START: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:Garbage|Minor|Major) Collection \(").unwrap());
+static RE_SHENANDOAH_START: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Concurrent reset|Pause Init Mark").unwrap());
+static RE_EDEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Eden regions: (\d+)->(\d+)\((\d+)\)").unwrap());
+static RE_SURVIVOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Survivor regions: (\d+)->(\d+)\((\d+)\)").unwrap());
+static RE_OLD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Old regions: (\d+)->(\d+)").unwrap());
+static RE_HUMONGOUS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Humongous regions: (\d+)->(\d+)").unwrap());
+// Parallel / Serial GC は世代ごとのサイズを K/M 単位で出力する
+// (JDK 17 以降は "PSYoungGen: 65536K(76288K)->10720K(76288K)" のように変更前の容量も付く)
+static RE_GENERATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(PSYoungGen|DefNew|ParOldGen|PSOldGen|Tenured): (\d+)([KMG])(?:\(\d+[KMG]\))?->(\d+)([KMG])\((\d+)([KMG])\)",
+    )
+    .unwrap()
+});
 
-                let detected = detect_gc_type(&log);
-                if detected != GcType::Unknown {
-                    current.gc_type = detected.clone();
-
-                    if re_pause.is_match(&log) {
-                        current.has_pause = true;
-                    } else if matches!(detected, GcType::Concurrent) {
-                        // Concurrent GC はヒープ構成が出ないのでこの時点で push
-                        events.push(current.clone()); // Cloneが必要
-                        current = GCEvent {
-                            time: None,
-                            gc_type: GcType::Unknown,
-                            has_pause: false,
-                            pause_time_ms: None,
-                            eden_before: None,
-                            eden_after: None,
-                            eden_total: None,
-                            survivor_before: None,
-                            survivor_after: None,
-                            survivor_total: None,
-                            old_before: None,
-                            old_after: None,
-                            humongous_before: None,
-                            humongous_after: None,
-                        };
-                    }
-                }
+// 検出したログ形式に応じてパーサを切り替える
+pub fn parse_gc_log<I>(lines: I, info: &LogInfo) -> Result<GcLog, anyhow::Error>
+where
+    I: IntoIterator<Item = String>,
+{
+    match info.format {
+        LogFormat::Jdk8 => parse_legacy_gc_events(lines),
+        LogFormat::Unified | LogFormat::Unknown => parse_gc_events(lines),
+    }
+}
+
+// GCログから構造体にマッピングして、データのデシリアライズを行う
+pub fn parse_gc_events<I>(lines: I) -> Result<GcLog, anyhow::Error>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parser = GcEventParser::default();
+    let mut gc_log = GcLog::default();
+    for log in lines {
+        gc_log.extend(parser.feed(&log));
+    }
+    gc_log.extend(parser.finish());
+    Ok(gc_log)
+}
+
+// ログ形式ごとのパーサを1行ずつ駆動するためのラッパー (--follow で使う)
+#[derive(Debug)]
+pub enum GcLogParser {
+    Unified(Box<GcEventParser>),
+    Legacy(Box<LegacyGcParser>),
+}
+
+impl GcLogParser {
+    pub fn new(info: &LogInfo) -> Self {
+        match info.format {
+            LogFormat::Jdk8 => GcLogParser::Legacy(Box::default()),
+            LogFormat::Unified | LogFormat::Unknown => GcLogParser::Unified(Box::default()),
+        }
+    }
+
+    pub fn feed(&mut self, log: String) -> GcLog {
+        match self {
+            GcLogParser::Unified(parser) => parser.feed(&log),
+            GcLogParser::Legacy(parser) => parser.feed(log),
+        }
+    }
+
+    // 追記が途切れたときに、保留中のイベントを確定させる
+    pub fn flush(&mut self) -> GcLog {
+        match self {
+            GcLogParser::Unified(parser) => parser.flush(),
+            GcLogParser::Legacy(parser) => parser.flush(),
+        }
+    }
+
+    pub fn finish(self) -> GcLog {
+        match self {
+            GcLogParser::Unified(parser) => parser.finish(),
+            GcLogParser::Legacy(parser) => parser.finish(),
+        }
+    }
+}
+
+// 統合ロギング形式のパーサの状態
+// 異なるGC(N)の行が交互に出力されても崩れないよう、処理中のイベントをGC IDごとに保持する
+#[derive(Debug, Default)]
+pub struct GcEventParser {
+    in_flight: HashMap<u64, GCEvent>,
+    // G1 の並行サイクル
+    cycles: HashMap<u64, ConcurrentCycle>,
+    // 並行サイクル (同じGC ID) の途中で実行された Pause Remark / Pause Cleanup
+    nested: HashMap<u64, GCEvent>,
+    // ZGC / Shenandoah のサイクルとして開始したGC ID
+    zgc_cycles: HashSet<u64>,
+    shenandoah_cycles: HashSet<u64>,
+    // 次に始まる Shenandoah のサイクルの原因 ("Trigger: ..." の行)
+    shenandoah_trigger: Option<GcCause>,
+    // [gc,cpu] の行は集計行の直後に出力されるので、確定したイベントはGC IDごとに保留し、
+    // 同じGC IDの [gc,cpu] の行か、次のGCの開始で確定させる
+    awaiting_cpu: HashMap<u64, GCEvent>,
+    // Heap after GC ブロックに入ったGC ID
+    after_heap_block: HashSet<u64>,
+    // [gc,phases,ref] の "SoftReference:" 等、Discovered の行が属する参照型
+    ref_sections: HashMap<u64, ReferenceSection>,
+    // [gc,heap,exit] の JVM 終了時のヒープの状態
+    exit: Option<JvmExitSummary>,
+}
+
+impl GcEventParser {
+    // 1行を読み込み、確定したイベントと並行サイクルを返す
+    pub fn feed(&mut self, log: &str) -> GcLog {
+        let mut out = GcLog::default();
+
+        let gc_id = RE_GC_ID
+            .captures(log)
+            .and_then(|caps| caps[1].parse::<u64>().ok());
+        if let Some(id) = gc_id
+            && let Some(caps) = RE_CPU_TIME.captures(log)
+            && let Some(mut event) = self.awaiting_cpu.remove(&id)
+        {
+            event.set_cpu_times(
+                caps[1].parse().unwrap_or(0.0),
+                caps[2].parse().unwrap_or(0.0),
+                caps[3].parse().unwrap_or(0.0),
+            );
+            out.events.push(event);
+            return out;
+        }
+
+        // JVM 終了時の "[gc,heap,exit] Heap" とそれに続くブロック
+        if RE_HEAP_EXIT.is_match(log) {
+            if RE_HEAP_EXIT_START.is_match(log) {
+                let (decorations, _) = parse_decorations(log);
+                self.exit = Some(JvmExitSummary {
+                    time: decorations.time,
+                    uptime_secs: decorations.uptime_secs,
+                    ..Default::default()
+                });
+            } else if let Some(exit) = self.exit.as_mut() {
+                exit.apply_line(log);
             }
-            if re_eden.is_match(&log) {
-                if let Some(caps) = Regex::new(r"Eden regions: (\d+)->(\d+)\((\d+)\)")
-                    .unwrap()
-                    .captures(&log)
-                {
-                    current.eden_before = caps.get(1).map(|m| m.as_str().parse().unwrap());
-                    current.eden_after = caps.get(2).map(|m| m.as_str().parse().unwrap());
-                    current.eden_total = caps.get(3).map(|m| m.as_str().parse().unwrap());
-                }
+            return out;
+        }
+
+        // GC(N) を含まない行はイベントに紐付けられないので読み飛ばす
+        let Some(gc_id) = gc_id else {
+            if let Some(cause) = detect_shenandoah_trigger(log) {
+                self.shenandoah_trigger = Some(cause);
             }
+            return out;
+        };
 
-            if let Some(caps) = re_pause_time.captures(&log) {
-                current.pause_time_ms = caps.get(1).map(|m| m.as_str().parse().unwrap());
+        // Shenandoah はサイクルの終了行が無いので、次のGC IDが始まった時点で確定させる
+        let mut finished_cycles: Vec<u64> = self
+            .shenandoah_cycles
+            .iter()
+            .copied()
+            .filter(|id| *id < gc_id)
+            .collect();
+        finished_cycles.sort_unstable();
+        for id in finished_cycles {
+            self.shenandoah_cycles.remove(&id);
+            if let Some(event) = self.in_flight.remove(&id) {
+                out.events.push(event);
             }
-            if re_survivor.is_match(&log) {
-                if let Some(caps) = Regex::new(r"Survivor regions: (\d+)->(\d+)\((\d+)\)")
-                    .unwrap()
-                    .captures(&log)
-                {
-                    current.survivor_before = caps.get(1).map(|m| m.as_str().parse().unwrap());
-                    current.survivor_after = caps.get(2).map(|m| m.as_str().parse().unwrap());
-                    current.survivor_total = caps.get(3).map(|m| m.as_str().parse().unwrap());
-                }
+        }
+
+        let detected = detect_gc_type(log);
+        let heap_block = heap_block_kind(log);
+        let is_new = !self.in_flight.contains_key(&gc_id) && !self.cycles.contains_key(&gc_id);
+        // 次のGCが始まった時点で、前のGCの [gc,cpu] の行はもう出力されない
+        if is_new && detected != GcType::Unknown {
+            out.events.extend(self.flush().events);
+        }
+        if matches!(detected, GcType::Remark | GcType::Cleanup)
+            && self.cycles.contains_key(&gc_id)
+            && !self.nested.contains_key(&gc_id)
+        {
+            self.nested.insert(gc_id, new_event(gc_id, &detected, log));
+        } else if is_new && RE_SHENANDOAH_START.is_match(log) {
+            // Shenandoah のサイクルは並行フェーズのイベントとし、サイクル中の Pause は別のイベントにする
+            let mut cycle = new_event(gc_id, &GcType::Concurrent, log);
+            cycle.has_pause = false;
+            cycle.gc_cause = self.shenandoah_trigger.take();
+            self.in_flight.insert(gc_id, cycle);
+            self.shenandoah_cycles.insert(gc_id);
+        } else if detected == GcType::Concurrent && is_new && !RE_ZGC_START.is_match(log) {
+            let (decorations, _) = parse_decorations(log);
+            self.cycles.insert(
+                gc_id,
+                ConcurrentCycle {
+                    gc_id: Some(gc_id),
+                    start: decorations.time,
+                    start_uptime_secs: decorations.uptime_secs,
+                    ..Default::default()
+                },
+            );
+        } else if heap_block == Some(false) && is_new {
+            // Heap before GC ブロックは Pause の開始行より前に出力されるので、種別未定のイベントを先に作る
+            self.in_flight
+                .insert(gc_id, new_event(gc_id, &GcType::Unknown, log));
+        } else if detected != GcType::Unknown
+            && let Some(pending) = self
+                .in_flight
+                .get_mut(&gc_id)
+                .filter(|e| e.gc_type == GcType::Unknown)
+        {
+            let event = new_event(gc_id, &detected, log);
+            pending.gc_type = event.gc_type;
+            pending.gc_cause = event.gc_cause;
+            pending.young_phase = event.young_phase;
+            pending.has_pause = event.has_pause;
+        } else if detected != GcType::Unknown && !self.in_flight.contains_key(&gc_id) {
+            self.in_flight
+                .insert(gc_id, new_event(gc_id, &detected, log));
+            if RE_ZGC_START.is_match(log) {
+                self.zgc_cycles.insert(gc_id);
             }
-            if re_old.is_match(&log) {
-                if let Some(caps) = Regex::new(r"Old regions: (\d+)->(\d+)")
-                    .unwrap()
-                    .captures(&log)
-                {
-                    current.old_before = caps.get(1).map(|m| m.as_str().parse().unwrap());
-                    current.old_after = caps.get(2).map(|m| m.as_str().parse().unwrap());
+        }
+
+        // 並行サイクル中の Pause は、サイクルとは別のイベントとして所要時間を記録する
+        if let Some(pause) = self.nested.get_mut(&gc_id) {
+            apply_decorations(pause, log);
+            apply_reference_line(pause, log, self.ref_sections.entry(gc_id).or_default());
+            if apply_pause_line(pause, log, &detected) {
+                self.ref_sections.remove(&gc_id);
+                if let Some(pause) = self.nested.remove(&gc_id) {
+                    self.await_cpu(gc_id, pause, &mut out);
                 }
             }
-            if re_humongous.is_match(&log) {
-                if let Some(caps) = Regex::new(r"Humongous regions: (\d+)->(\d+)")
-                    .unwrap()
-                    .captures(&log)
-                {
-                    current.humongous_before = caps.get(1).map(|m| m.as_str().parse().unwrap());
-                    current.humongous_after = caps.get(2).map(|m| m.as_str().parse().unwrap());
-
-                    if current.has_pause {
-                        events.push(current);
-                    }
-                    current = GCEvent {
-                        time: None,
-                        gc_type: GcType::Unknown,
-                        has_pause: false,
-                        pause_time_ms: None,
-                        eden_before: None,
-                        eden_after: None,
-                        eden_total: None,
-                        survivor_before: None,
-                        survivor_after: None,
-                        survivor_total: None,
-                        old_before: None,
-                        old_after: None,
-                        humongous_before: None,
-                        humongous_after: None,
-                    };
-                }
+            return out;
+        }
+
+        if let Some(cycle) = self.cycles.get_mut(&gc_id) {
+            if apply_concurrent_line(cycle, log) {
+                out.cycles.extend(self.cycles.remove(&gc_id));
+            }
+            return out;
+        }
+
+        let Some(current) = self.in_flight.get_mut(&gc_id) else {
+            return out;
+        };
+        apply_decorations(current, log);
+        if heap_block == Some(true) {
+            self.after_heap_block.insert(gc_id);
+        }
+        current.evacuation_failure = current
+            .evacuation_failure
+            .max(detect_evacuation_failure(log));
+        current
+            .humongous_object_sizes
+            .extend(detect_humongous_object_size(log));
+        apply_tenuring_line(current, log);
+        apply_reference_line(current, log, self.ref_sections.entry(gc_id).or_default());
+        let after_gc = self.after_heap_block.contains(&gc_id);
+        apply_heap_block_line(current, log, after_gc);
+        apply_metaspace_line(current, log, after_gc);
+
+        // ZGC / Shenandoah はサイクル単位でフェーズを集計する
+        let finished = if self.zgc_cycles.contains(&gc_id) {
+            apply_zgc_line(current, log)
+        } else if self.shenandoah_cycles.contains(&gc_id) {
+            let pause = apply_shenandoah_line(current, log);
+            let finished = pause
+                .as_ref()
+                .is_some_and(|p| matches!(p.gc_type, GcType::Degenerated | GcType::Full));
+            out.events.extend(pause);
+            finished
+        } else {
+            apply_pause_line(current, log, &detected)
+        };
+
+        if finished {
+            self.zgc_cycles.remove(&gc_id);
+            self.shenandoah_cycles.remove(&gc_id);
+            self.after_heap_block.remove(&gc_id);
+            self.ref_sections.remove(&gc_id);
+            let mut event = self.in_flight.remove(&gc_id).unwrap();
+            event.heap_block_issues = check_heap_block(&event);
+            // Concurrent GC はPauseを伴わないが、サイクル単位で記録する
+            if event.has_pause || matches!(event.gc_type, GcType::Concurrent) {
+                self.await_cpu(gc_id, event, &mut out);
+            }
+        }
+        out
+    }
+
+    // 同じGC IDで [gc,cpu] を待っているイベント (並行サイクル中の Remark の後の Cleanup 等) があれば、
+    // [gc,cpu] の行が出力されないログなので上書きせずに先に確定させる
+    fn await_cpu(&mut self, gc_id: u64, event: GCEvent, out: &mut GcLog) {
+        out.events.extend(self.awaiting_cpu.insert(gc_id, event));
+    }
+
+    // [gc,cpu] の行を待っているイベントを確定させる
+    // (-Xlog:gc のように [gc,cpu] が無いログを --follow で追うときは、追記が途切れた時点で呼ぶ)
+    pub fn flush(&mut self) -> GcLog {
+        let mut events: Vec<GCEvent> = self.awaiting_cpu.drain().map(|(_, e)| e).collect();
+        events.sort_by_key(|e| e.gc_id);
+        GcLog {
+            events,
+            ..Default::default()
+        }
+    }
+
+    // ログ末尾で終わった Shenandoah のサイクルも記録する
+    pub fn finish(mut self) -> GcLog {
+        let awaiting = self.flush().events;
+        let mut remaining: Vec<u64> = self.shenandoah_cycles.into_iter().collect();
+        remaining.sort_unstable();
+        GcLog {
+            events: awaiting
+                .into_iter()
+                .chain(
+                    remaining
+                        .into_iter()
+                        .filter_map(|id| self.in_flight.remove(&id)),
+                )
+                .collect(),
+            exit: self.exit,
+            ..Default::default()
+        }
+    }
+}
+
+fn new_event(gc_id: u64, gc_type: &GcType, log: &str) -> GCEvent {
+    GCEvent {
+        gc_id: Some(gc_id),
+        gc_type: gc_type.clone(),
+        gc_cause: detect_gc_cause(log),
+        young_phase: detect_young_phase(log),
+        has_pause: RE_PAUSE.is_match(log),
+        ..Default::default()
+    }
+}
+
+// 並行サイクルのフェーズを記録し、"Concurrent Cycle 47.991ms" (サイクル全体) を読んだら true を返す
+fn apply_concurrent_line(cycle: &mut ConcurrentCycle, log: &str) -> bool {
+    let (decorations, _) = parse_decorations(log);
+    if decorations.time.is_some() {
+        cycle.end = decorations.time;
+    }
+    if decorations.uptime_secs.is_some() {
+        cycle.end_uptime_secs = decorations.uptime_secs;
+    }
+    if log.contains("Concurrent Mark Abort") {
+        cycle.aborted = true;
+    }
+
+    let Some(caps) = RE_CONCURRENT_PHASE.captures(log) else {
+        return false;
+    };
+    let name = &caps[1];
+    let duration_ms: f64 = caps[2].parse().unwrap_or(0.0);
+    if name == "Concurrent Cycle" || name == "Concurrent Undo Cycle" {
+        cycle.duration_ms = Some(duration_ms);
+        return true;
+    }
+    cycle.phases.push(GcPhase::new(name, duration_ms));
+    false
+}
+
+// "GC(N)" の後ろの空白の数から入れ子の深さを求める (区切りの1文字 + 2文字ずつのインデント)
+fn phase_depth(indent: &str) -> usize {
+    ((indent.len() - 1) / 2).saturating_sub(1)
+}
+
+fn apply_decorations(event: &mut GCEvent, log: &str) {
+    let (decorations, _) = parse_decorations(log);
+    if decorations.time.is_some() {
+        event.time = decorations.time;
+    }
+    if decorations.uptime_secs.is_some() {
+        event.uptime_secs = decorations.uptime_secs;
+    }
+}
+
+// G1 / Parallel / Serial のPause単位の行をイベントに反映する
+// 開始行と同じ種別で所要時間付きの行 (サマリ行) を読んだ場合は true を返す
+fn apply_pause_line(current: &mut GCEvent, log: &str, detected: &GcType) -> bool {
+    // 同じGC IDの別種Pause (Remark等) のサマリ行は対象外
+    if let Some(caps) = RE_PAUSE_SUMMARY
+        .captures(log)
+        .filter(|_| *detected == current.gc_type)
+    {
+        current.heap_used_before = parse_size(&caps[1], &caps[2]);
+        current.heap_used_after = parse_size(&caps[3], &caps[4]);
+        current.heap_committed = parse_size(&caps[5], &caps[6]);
+        current.pause_time_ms = caps[7].parse().ok();
+    }
+    if let Some(caps) = RE_EDEN.captures(log) {
+        current.eden_before = caps[1].parse().ok();
+        current.eden_after = caps[2].parse().ok();
+        current.eden_total = caps[3].parse().ok();
+    }
+    if let Some(caps) = RE_SURVIVOR.captures(log) {
+        current.survivor_before = caps[1].parse().ok();
+        current.survivor_after = caps[2].parse().ok();
+        current.survivor_total = caps[3].parse().ok();
+    }
+    if let Some(caps) = RE_OLD.captures(log) {
+        current.old_before = caps[1].parse().ok();
+        current.old_after = caps[2].parse().ok();
+    }
+    if let Some(caps) = RE_HUMONGOUS.captures(log) {
+        current.humongous_before = caps[1].parse().ok();
+        current.humongous_after = caps[2].parse().ok();
+    }
+    if let Some(caps) = RE_PAUSE_PHASE.captures(log) {
+        let phase = GcPhase::new(&caps[2], caps[3].parse().unwrap_or(0.0));
+        push_phase(&mut current.phases, phase_depth(&caps[1]), phase);
+    } else if let Some(caps) = RE_WORKER_PHASE.captures(log) {
+        let workers = WorkerStats {
+            min: caps[3].parse().unwrap_or(0.0),
+            avg: caps[4].parse().unwrap_or(0.0),
+            max: caps[5].parse().unwrap_or(0.0),
+            diff: caps[6].parse().unwrap_or(0.0),
+            sum: caps[7].parse().unwrap_or(0.0),
+            workers: caps[8].parse().ok(),
+        };
+        let phase = GcPhase::with_workers(&caps[2], workers);
+        push_phase(&mut current.phases, phase_depth(&caps[1]), phase);
+    }
+    for caps in RE_GENERATION.captures_iter(log) {
+        let before = parse_size(&caps[2], &caps[3]);
+        let after = parse_size(&caps[4], &caps[5]);
+        let total = parse_size(&caps[6], &caps[7]);
+        match &caps[1] {
+            "PSYoungGen" | "DefNew" => {
+                current.young_gen_before = before;
+                current.young_gen_after = after;
+                current.young_gen_total = total;
+            }
+            _ => {
+                current.old_gen_before = before;
+                current.old_gen_after = after;
+                current.old_gen_total = total;
+            }
+        }
+    }
+
+    *detected == current.gc_type && RE_PAUSE_TIME.is_match(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EvacuationFailure;
+    use std::io::Cursor;
+
+    const SAMPLE: &str = include_str!("../../sample/sample_gc.log");
+
+    #[test]
+    fn skips_undecodable_lines() {
+        let mut bytes = Vec::new();
+        for (index, line) in SAMPLE.lines().enumerate() {
+            if index == 99 {
+                bytes.extend_from_slice(b"\xff\xfe broken line\r\n");
             }
+            bytes.extend_from_slice(line.as_bytes());
+            bytes.push(b'\n');
         }
+
+        let lines: Vec<String> = LogLines::new(Box::new(Cursor::new(bytes)))
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(lines.len(), SAMPLE.lines().count() + 1);
+        assert_eq!(lines[99], "\u{FFFD}\u{FFFD} broken line");
+
+        let expected = parse_gc_events(SAMPLE.lines().map(String::from)).unwrap();
+        let actual = parse_gc_events(lines).unwrap();
+        assert_eq!(expected.events.len(), 34);
+        assert_eq!(actual.events.len(), expected.events.len());
+    }
+
+    #[test]
+    fn keeps_interleaved_gc_ids_apart() {
+        let lines = [
+            "[2025-04-11T09:50:21.000+0900][4.000s][info][gc,marking] GC(5) Concurrent Cycle",
+            "[2025-04-11T09:50:21.001+0900][4.001s][info][gc,marking] GC(5) Concurrent Mark From Roots",
+            "[2025-04-11T09:50:21.002+0900][4.002s][info][gc,start] GC(6) Pause Young (Normal) (G1 Evacuation Pause)",
+            "[2025-04-11T09:50:21.010+0900][4.010s][info][gc,marking] GC(5) Concurrent Mark From Roots 12.000ms",
+            "[2025-04-11T09:50:21.011+0900][4.011s][info][gc,heap] GC(6) Eden regions: 10->0(12)",
+            "[2025-04-11T09:50:21.012+0900][4.012s][info][gc] GC(6) Pause Young (Normal) (G1 Evacuation Pause) 100M->20M(1024M) 5.000ms",
+            "[2025-04-11T09:50:21.030+0900][4.030s][info][gc] GC(5) Concurrent Cycle 30.000ms",
+        ];
+        let log = parse_gc_events(lines.iter().map(|line| line.to_string())).unwrap();
+
+        assert_eq!(log.events.len(), 1);
+        let young = &log.events[0];
+        assert_eq!(young.gc_id, Some(6));
+        assert_eq!(young.gc_type, GcType::Young);
+        assert_eq!(young.pause_time_ms, Some(5.0));
+        assert_eq!(
+            (young.eden_before, young.eden_after),
+            (Some(10.0), Some(0.0))
+        );
+
+        assert_eq!(log.cycles.len(), 1);
+        let cycle = &log.cycles[0];
+        assert_eq!(cycle.gc_id, Some(5));
+        assert_eq!(cycle.duration_ms, Some(30.0));
+        assert_eq!(cycle.phases.len(), 1);
+        assert_eq!(cycle.phases[0].name, "Concurrent Mark From Roots");
+    }
+
+    #[test]
+    fn keeps_most_severe_evacuation_failure() {
+        let lines = [
+            "[7.100s][info][gc,start] GC(12) Pause Young (Normal) (G1 Evacuation Pause)",
+            "[7.120s][info][gc] GC(12) To-space exhausted",
+            "[7.121s][info][gc,phases] GC(12)   Post Evacuate Collection Set: 9.1ms",
+            "[7.121s][info][gc,phases] GC(12)     Evacuation Failure: 1.2ms",
+            "[7.123s][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) 6010M->5987M(6144M) 45.107ms",
+            "[8.100s][info][gc,start] GC(13) Pause Young (Normal) (G1 Evacuation Pause)",
+            "[8.121s][info][gc,phases] GC(13)     Evacuation Failure: 0.8ms",
+            "[8.123s][info][gc] GC(13) Pause Young (Normal) (G1 Evacuation Pause) (Evacuation Failure: Pinned) 2010M->1987M(6144M) 12.310ms",
+        ];
+        let log = parse_gc_events(lines.iter().map(|line| line.to_string())).unwrap();
+        let failures: Vec<_> = log.events.iter().map(|e| e.evacuation_failure).collect();
+        assert_eq!(
+            failures,
+            [
+                Some(EvacuationFailure::ToSpaceExhausted),
+                Some(EvacuationFailure::Pinned)
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_remark_and_cleanup_out_of_concurrent_cycles() {
+        let log = parse_gc_events(SAMPLE.lines().map(String::from)).unwrap();
+        let count = |gc_type: GcType| log.events.iter().filter(|e| e.gc_type == gc_type).count();
+        assert_eq!(count(GcType::Young), 16);
+        assert_eq!(count(GcType::Remark), 9);
+        assert_eq!(count(GcType::Cleanup), 9);
+        assert_eq!(log.cycles.len(), 9);
+
+        // 並行サイクル GC(3) の途中の Pause は、それぞれの集計行の所要時間を持つ別のイベントになる
+        let pauses: Vec<(GcType, Option<f64>, Option<f64>)> = log
+            .events
+            .iter()
+            .filter(|e| e.gc_id == Some(3))
+            .map(|e| (e.gc_type.clone(), e.pause_time_ms, e.heap_used_after))
+            .collect();
+        assert_eq!(
+            pauses,
+            [
+                (GcType::Remark, Some(3.618), parse_size("21", "M")),
+                (GcType::Cleanup, Some(0.257), parse_size("21", "M")),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_remark_and_cleanup_without_cpu_lines() {
+        // [gc,cpu] を出力しないログ (-Xlog:gc*,gc+cpu=off) でも、Remark と Cleanup はそれぞれ別のイベントになる
+        let lines = SAMPLE
+            .lines()
+            .filter(|line| !line.contains("[gc,cpu"))
+            .map(String::from);
+        let log = parse_gc_events(lines).unwrap();
+        let count = |gc_type: GcType| log.events.iter().filter(|e| e.gc_type == gc_type).count();
+        assert_eq!(count(GcType::Young), 16);
+        assert_eq!(count(GcType::Remark), 9);
+        assert_eq!(count(GcType::Cleanup), 9);
+
+        let pauses: Vec<(GcType, Option<f64>)> = log
+            .events
+            .iter()
+            .filter(|e| e.gc_id == Some(3))
+            .map(|e| (e.gc_type.clone(), e.pause_time_ms))
+            .collect();
+        assert_eq!(
+            pauses,
+            [
+                (GcType::Remark, Some(3.618)),
+                (GcType::Cleanup, Some(0.257))
+            ]
+        );
+        assert!(log.events.iter().all(|e| e.cpu_real_secs.is_none()));
+    }
+
+    #[test]
+    fn classifies_mixed_and_prepare_mixed_pauses() {
+        let lines = [
+            "[9.000s][info][gc,start] GC(30) Pause Young (Prepare Mixed) (G1 Evacuation Pause)",
+            "[9.012s][info][gc] GC(30) Pause Young (Prepare Mixed) (G1 Evacuation Pause) 2100M->1900M(6144M) 12.000ms",
+            "[9.500s][info][gc,start] GC(31) Pause Young (Mixed) (G1 Evacuation Pause)",
+            "[9.530s][info][gc] GC(31) Pause Young (Mixed) (G1 Evacuation Pause) 2000M->1200M(6144M) 30.000ms",
+        ];
+        let log = parse_gc_events(lines.iter().map(|line| line.to_string())).unwrap();
+        let types: Vec<(GcType, Option<f64>)> = log
+            .events
+            .iter()
+            .map(|e| (e.gc_type.clone(), e.pause_time_ms))
+            .collect();
+        assert_eq!(
+            types,
+            [
+                (GcType::PrepareMixed, Some(12.0)),
+                (GcType::Mixed, Some(30.0))
+            ]
+        );
+    }
+
+    #[test]
+    fn records_concurrent_cycle_phases() {
+        let log = parse_gc_events(SAMPLE.lines().map(String::from)).unwrap();
+        let cycle = log.cycles.iter().find(|c| c.gc_id == Some(3)).unwrap();
+        assert_eq!(cycle.duration_ms, Some(37.466));
+        assert!(!cycle.aborted);
+        // 入れ子の親の "Concurrent Mark (3.610s, 3.613s) 3.334ms" は含めない
+        let phases: Vec<(&str, f64)> = cycle
+            .phases
+            .iter()
+            .map(|p| (p.name.as_str(), p.duration_ms))
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                ("Concurrent Clear Claimed Marks", 0.021),
+                ("Concurrent Scan Root Regions", 6.975),
+                ("Concurrent Mark From Roots", 3.206),
+                ("Concurrent Preclean", 0.097),
+                ("Concurrent Rebuild Remembered Sets", 0.398),
+                ("Concurrent Cleanup for Next Mark", 22.590),
+            ]
+        );
+        assert_eq!(
+            (cycle.start_uptime_secs, cycle.end_uptime_secs),
+            (Some(3.603), Some(3.640))
+        );
+    }
+
+    #[test]
+    fn marks_aborted_concurrent_cycles() {
+        // マーキング中に Full GC が走ると "Concurrent Mark Abort" が出力される
+        let lines = [
+            "[5.000s][info][gc] GC(8) Concurrent Cycle",
+            "[5.001s][info][gc,marking] GC(8) Concurrent Mark From Roots",
+            "[5.300s][info][gc,marking] GC(8) Concurrent Mark Abort",
+            "[5.301s][info][gc] GC(8) Concurrent Cycle 301.000ms",
+        ];
+        let log = parse_gc_events(lines.iter().map(|line| line.to_string())).unwrap();
+        assert_eq!(log.cycles.len(), 1);
+        let cycle = &log.cycles[0];
+        assert!(cycle.aborted);
+        assert_eq!(cycle.duration_ms, Some(301.0));
+        assert_eq!(cycle.end_uptime_secs, Some(5.301));
+    }
+
+    #[test]
+    fn nests_pause_phases_by_indent() {
+        assert_eq!(phase_depth(" "), 0);
+        assert_eq!(phase_depth("   "), 0);
+        assert_eq!(phase_depth("     "), 1);
+        assert_eq!(phase_depth("       "), 2);
+
+        let log = parse_gc_events(SAMPLE.lines().map(String::from)).unwrap();
+        let event = log.events.iter().find(|e| e.gc_id == Some(1)).unwrap();
+        let top: Vec<(&str, f64)> = event
+            .phases
+            .iter()
+            .map(|p| (p.name.as_str(), p.duration_ms))
+            .collect();
+        assert_eq!(
+            top,
+            [
+                ("Pre Evacuate Collection Set", 0.1),
+                ("Evacuate Collection Set", 15.6),
+                ("Post Evacuate Collection Set", 1.0),
+                ("Other", 0.3),
+            ]
+        );
+
+        let pre = &event.phases[0];
+        assert_eq!(pre.children.len(), 3);
+        assert_eq!(pre.children[0].name, "Prepare TLABs");
+
+        // ワーカー統計の行は Evacuate Collection Set の子になり、平均を所要時間とする
+        let evacuate = &event.phases[1];
+        let object_copy = evacuate
+            .children
+            .iter()
+            .find(|p| p.name == "Object Copy")
+            .unwrap();
+        assert_eq!(object_copy.duration_ms, 7.3);
+        let workers = object_copy.workers.as_ref().unwrap();
+        assert_eq!(
+            (workers.max, workers.sum, workers.workers),
+            (13.5, 29.0, Some(4))
+        );
+        let total = evacuate.children.last().unwrap();
+        assert_eq!(total.name, "GC Worker Total");
+        assert!(total.is_aggregate());
+
+        let post = &event.phases[2];
+        assert_eq!(post.children.len(), 13);
+        assert!(post.children.iter().all(|p| p.children.is_empty()));
+        assert!(event.phases[3].children.is_empty());
+    }
+
+    #[test]
+    fn takes_pause_and_heap_from_summary_line() {
+        // "Evacuate Collection Set: 19.4ms" 等のフェーズの行ではなく、最後の [gc] の集計行の値を使う
+        let log = parse_gc_events(SAMPLE.lines().map(String::from)).unwrap();
+        let first = &log.events[0];
+        assert_eq!(first.gc_id, Some(0));
+        assert_eq!(first.pause_time_ms, Some(21.425));
+        assert_eq!(first.heap_used_before, parse_size("307", "M"));
+        assert_eq!(first.heap_used_after, parse_size("17", "M"));
+        assert_eq!(first.heap_committed, parse_size("6144", "M"));
+        assert_eq!(parse_size("17", "M"), Some(17.0 * MB));
+    }
+
+    #[test]
+    fn parses_parallel_generation_sizes() {
+        let log = parse_gc_events(
+            include_str!("../../sample/sample_parallel.log")
+                .lines()
+                .map(String::from),
+        )
+        .unwrap();
+        assert_eq!(log.events.len(), 14);
+        let fulls: Vec<Option<u64>> = log
+            .events
+            .iter()
+            .filter(|e| e.gc_type == GcType::Full)
+            .map(|e| e.gc_id)
+            .collect();
+        assert_eq!(fulls, [Some(6), Some(12)]);
+
+        let young = &log.events[0];
+        assert_eq!(young.gc_type, GcType::Young);
+        assert_eq!(young.gc_cause, Some(GcCause::AllocationFailure));
+        assert_eq!(young.young_gen_before, parse_size("132734", "K"));
+        assert_eq!(young.young_gen_after, parse_size("6484", "K"));
+        assert_eq!(young.young_gen_total, parse_size("153600", "K"));
+        assert_eq!(young.old_gen_before, Some(0.0));
+        assert_eq!(young.old_gen_after, parse_size("11380", "K"));
+        assert_eq!(young.old_gen_total, parse_size("349696", "K"));
+
+        let full = log.events.iter().find(|e| e.gc_id == Some(6)).unwrap();
+        assert_eq!(full.gc_cause, Some(GcCause::Ergonomics));
+        assert_eq!(full.pause_time_ms, Some(92.353));
+        assert_eq!(full.young_gen_after, Some(0.0));
+        assert_eq!(full.old_gen_before, parse_size("112031", "K"));
+        assert_eq!(full.old_gen_after, parse_size("50413", "K"));
+    }
+
+    #[test]
+    fn parses_serial_generation_sizes() {
+        // JDK 17 以降は変更前の容量も付く
+        let lines = [
+            "[3.000s][info][gc,start] GC(3) Pause Young (Allocation Failure)",
+            "[3.030s][info][gc,heap] GC(3) DefNew: 69952K(78656K)->8704K(78656K) Eden: 69952K(69952K)->0K(69952K) From: 0K(8704K)->8704K(8704K)",
+            "[3.030s][info][gc,heap] GC(3) Tenured: 11317K(174784K)->20021K(174784K)",
+            "[3.030s][info][gc] GC(3) Pause Young (Allocation Failure) 79M->28M(247M) 30.252ms",
+            "[4.000s][info][gc,start] GC(4) Pause Full (System.gc())",
+            "[4.090s][info][gc,heap] GC(4) DefNew: 40000K->0K(78656K)",
+            "[4.090s][info][gc,heap] GC(4) Tenured: 20021K->15000K(174784K)",
+            "[4.090s][info][gc] GC(4) Pause Full (System.gc()) 59M->14M(247M) 90.000ms",
+        ];
+        let log = parse_gc_events(lines.iter().map(|line| line.to_string())).unwrap();
+        assert_eq!(log.events.len(), 2);
+
+        let young = &log.events[0];
+        assert_eq!(young.gc_type, GcType::Young);
+        assert_eq!(young.young_gen_before, parse_size("69952", "K"));
+        assert_eq!(young.young_gen_after, parse_size("8704", "K"));
+        assert_eq!(young.young_gen_total, parse_size("78656", "K"));
+        assert_eq!(young.old_gen_before, parse_size("11317", "K"));
+        assert_eq!(young.old_gen_after, parse_size("20021", "K"));
+        assert_eq!(young.old_gen_total, parse_size("174784", "K"));
+
+        let full = &log.events[1];
+        assert_eq!(full.gc_type, GcType::Full);
+        assert_eq!(full.gc_cause, Some(GcCause::SystemGc));
+        assert_eq!(full.young_gen_after, Some(0.0));
+        assert_eq!(full.old_gen_after, parse_size("15000", "K"));
+    }
+
+    #[test]
+    fn keeps_nested_pauses_without_cpu_lines() {
+        // -Xlog:gc のように [gc,cpu] が無いと、Remark と Cleanup は同じGC IDで確定を待つ
+        let lines = [
+            "[3.500s][info][gc] GC(2) Pause Young (Concurrent Start) (Metadata GC Threshold) 184M->18M(6144M) 13.334ms",
+            "[3.603s][info][gc] GC(3) Concurrent Cycle",
+            "[3.614s][info][gc] GC(3) Pause Remark 21M->21M(6144M) 3.618ms",
+            "[3.615s][info][gc] GC(3) Pause Cleanup 21M->21M(6144M) 0.257ms",
+            "[3.640s][info][gc] GC(3) Concurrent Cycle 37.466ms",
+            "[4.000s][info][gc] GC(4) Pause Young (Normal) (G1 Evacuation Pause) 230M->26M(6144M) 27.746ms",
+        ];
+        let log = parse_gc_events(lines.iter().map(|line| line.to_string())).unwrap();
+        let pauses: Vec<(Option<u64>, GcType, Option<f64>)> = log
+            .events
+            .iter()
+            .map(|e| (e.gc_id, e.gc_type.clone(), e.pause_time_ms))
+            .collect();
+        assert_eq!(
+            pauses,
+            [
+                (Some(2), GcType::Young, Some(13.334)),
+                (Some(3), GcType::Remark, Some(3.618)),
+                (Some(3), GcType::Cleanup, Some(0.257)),
+                (Some(4), GcType::Young, Some(27.746)),
+            ]
+        );
+        assert_eq!(log.cycles.len(), 1);
     }
-    Ok(events)
 }
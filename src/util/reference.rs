@@ -0,0 +1,231 @@
+use crate::model::GCEvent;
+use regex::Regex;
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+// Reference Processing 全体の所要時間
+// G1: "GC(0)     Reference Processing: 0.6ms" / Remark: "GC(3) Reference Processing 0.027ms" / JDK 8: "[Ref Proc: 0.6 ms]"
+static RE_REF_PROCESSING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:Reference Processing:?|\[Ref Proc:) (\d+\.\d+) ?ms").unwrap());
+// JDK 9-10 [gc,phases,ref] "SoftReference: 0.1ms" / JDK 11+ "Reconsider SoftReferences: 0.0ms", "Notify PhantomReferences: 0.1ms"
+// (並行の "Preclean SoftReferences" はサイクル側の行なので Pause には含めない)
+static RE_REF_TIME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(Soft|Weak|Final|Phantom)References?: (\d+\.\d+)ms").unwrap());
+// JDK 11+ の FinalReference の処理は "Notify and keep alive finalizable: 0.3ms"
+static RE_FINALIZABLE_TIME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Notify and keep alive finalizable: (\d+\.\d+)ms").unwrap());
+// [gc,ref] "Ref Counts: Soft: 0 Weak: 389 Final: 25 Phantom: 4"
+static RE_REF_COUNTS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"Ref Counts: Soft: (\d+) Weak: (\d+) Final: (\d+) Phantom: (\d+)").unwrap()
+});
+// JDK 11+ [gc,phases,ref] の "SoftReference:" に続く "Discovered: 112" (Cleared の行は読み飛ばす)
+static RE_REF_SECTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"GC\(\d+\) +(Soft|Weak|Final|Phantom)Reference:\s*$").unwrap());
+static RE_REF_DISCOVERED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"GC\(\d+\) +Discovered: (\d+)").unwrap());
+// JDK 8 の -XX:+PrintReferenceGC: "[SoftReference, 0 refs, 0.0000080 secs]"
+// (PhantomReference は "0 refs, 0 refs," と2つ出力される)
+static RE_LEGACY_REF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\[(Soft|Weak|Final|Phantom)Reference, (\d+) refs, (?:\d+ refs, )?(\d+\.\d+) secs\]",
+    )
+    .unwrap()
+});
+// 世代ごとの内訳の途中に割り込む、タイムスタンプ付きの参照型ごとの出力
+// "[ParNew2019-01-01T00:00:01.010+0000: 1.010: [SoftReference, 0 refs, 0.0000080 secs]...: 10000K->..."
+static RE_LEGACY_REF_ENTRY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\.\d{3}[+-]\d{4}: )?(?:\d+\.\d+: )?\[(?:(?:Soft|Weak|Final|Phantom)Reference|JNI Weak Reference)(?:, \d+ refs)*, \d+\.\d+ secs\]",
+    )
+    .unwrap()
+});
+// [gc,stringtable] "Cleaned string and symbol table, strings: 15464 processed, 0 removed, symbols: 56772 processed, 154 removed"
+static RE_STRING_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"strings: (\d+) processed, (\d+) removed(?:, symbols: (\d+) processed, (\d+) removed)?",
+    )
+    .unwrap()
+});
+
+// Discovered の行がどの参照型のものかを覚えておくための、直前の "SoftReference:" 等
+pub type ReferenceSection = Option<String>;
+
+// 参照型ごとの件数・所要時間と、String / Symbol テーブルの掃除の件数をイベントに反映する
+pub fn apply_reference_line(event: &mut GCEvent, line: &str, section: &mut ReferenceSection) {
+    if let Some(caps) = RE_REF_PROCESSING.captures(line) {
+        event.ref_processing_ms = caps[1].parse().ok();
+    } else if let Some(caps) = RE_REF_COUNTS.captures(line) {
+        for (kind, index) in [("Soft", 1), ("Weak", 2), ("Final", 3), ("Phantom", 4)] {
+            *ref_count(event, kind) = caps[index].parse().ok();
+        }
+    } else if let Some(caps) = RE_REF_SECTION.captures(line) {
+        *section = Some(caps[1].to_string());
+    } else if let Some(caps) = RE_REF_DISCOVERED.captures(line) {
+        if let Some(kind) = section.take() {
+            *ref_count(event, &kind) = caps[1].parse().ok();
+        }
+    } else if let Some(caps) = RE_REF_TIME.captures(line) {
+        add_ref_time(event, &caps[1], caps[2].parse().unwrap_or(0.0));
+    } else if let Some(caps) = RE_FINALIZABLE_TIME.captures(line) {
+        add_ref_time(event, "Final", caps[1].parse().unwrap_or(0.0));
+    } else {
+        apply_string_table(event, line);
+    }
+}
+
+// JDK 8 の -XX:+PrintReferenceGC は参照型ごとの件数と所要時間が GC の行の中に続けて出力される
+pub fn apply_legacy_references(event: &mut GCEvent, text: &str) {
+    for caps in RE_LEGACY_REF.captures_iter(text) {
+        *ref_count(event, &caps[1]) = caps[2].parse().ok();
+        add_ref_time(
+            event,
+            &caps[1],
+            caps[3].parse::<f64>().unwrap_or(0.0) * 1000.0,
+        );
+    }
+    if let Some(caps) = RE_REF_PROCESSING.captures(text) {
+        event.ref_processing_ms = caps[1].parse().ok();
+    }
+    apply_string_table(event, text);
+}
+
+// 参照型ごとの出力を取り除き、"[ParNew: 10000K->1000K(20000K), ...]" の形に戻す
+pub fn strip_legacy_references(header: &str) -> Cow<'_, str> {
+    RE_LEGACY_REF_ENTRY.replace_all(header, "")
+}
+
+fn apply_string_table(event: &mut GCEvent, text: &str) {
+    if let Some(caps) = RE_STRING_TABLE.captures(text) {
+        event.strings_processed = caps[1].parse().ok();
+        event.strings_removed = caps[2].parse().ok();
+        event.symbols_processed = caps.get(3).and_then(|m| m.as_str().parse().ok());
+        event.symbols_removed = caps.get(4).and_then(|m| m.as_str().parse().ok());
+    }
+}
+
+fn ref_count<'a>(event: &'a mut GCEvent, kind: &str) -> &'a mut Option<u64> {
+    match kind {
+        "Soft" => &mut event.soft_refs,
+        "Weak" => &mut event.weak_refs,
+        "Final" => &mut event.final_refs,
+        _ => &mut event.phantom_refs,
+    }
+}
+
+// JDK 11+ は同じ参照型の処理が複数のフェーズに分かれて出力されるので合算する
+fn add_ref_time(event: &mut GCEvent, kind: &str, ms: f64) {
+    let time = match kind {
+        "Soft" => &mut event.soft_ref_ms,
+        "Weak" => &mut event.weak_ref_ms,
+        "Final" => &mut event.final_ref_ms,
+        _ => &mut event.phantom_ref_ms,
+    };
+    *time = Some(time.unwrap_or(0.0) + ms);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(lines: &[&str]) -> GCEvent {
+        let mut event = GCEvent::default();
+        let mut section = None;
+        for line in lines {
+            apply_reference_line(&mut event, line, &mut section);
+        }
+        event
+    }
+
+    #[test]
+    fn reads_unified_reference_phases() {
+        // sample_gc.log の G1 の Pause Young と Remark
+        let event =
+            apply(&["[2.148s][debug][gc,phases    ] GC(0)     Reference Processing: 0.6ms"]);
+        assert_eq!(event.ref_processing_ms, Some(0.6));
+        let event = apply(&["[3.614s][debug][gc,phases    ] GC(3) Reference Processing 0.027ms"]);
+        assert_eq!(event.ref_processing_ms, Some(0.027));
+
+        // JDK 11+ の -Xlog:gc+phases+ref=debug (同じ参照型の時間は合算する)
+        let event = apply(&[
+            "[7.954s][debug][gc,phases,ref] GC(6)     Reconsider SoftReferences: 0.1ms",
+            "[7.954s][debug][gc,phases,ref] GC(6)     Notify Soft/WeakReferences: 0.4ms",
+            "[7.954s][debug][gc,phases,ref] GC(6)     Notify and keep alive finalizable: 0.5ms",
+            "[7.954s][debug][gc,phases,ref] GC(6)     Notify PhantomReferences: 0.2ms",
+            "[7.954s][debug][gc,phases,ref] GC(6)     SoftReference:",
+            "[7.954s][debug][gc,phases,ref] GC(6)       Discovered: 112",
+            "[7.954s][debug][gc,phases,ref] GC(6)       Cleared: 0",
+            "[7.954s][debug][gc,phases,ref] GC(6)     FinalReference:",
+            "[7.954s][debug][gc,phases,ref] GC(6)       Discovered: 25",
+            "[7.954s][debug][gc,phases,ref] GC(6)       Cleared: 25",
+        ]);
+        assert_eq!(event.soft_ref_ms, Some(0.1));
+        assert_eq!(event.weak_ref_ms, Some(0.4));
+        assert_eq!(event.final_ref_ms, Some(0.5));
+        assert_eq!(event.phantom_ref_ms, Some(0.2));
+        assert_eq!(event.soft_refs, Some(112));
+        assert_eq!(event.final_refs, Some(25));
+        assert_eq!(event.weak_refs, None);
+
+        let event = apply(&[
+            "[7.954s][debug][gc,ref       ] GC(6) Ref Counts: Soft: 0 Weak: 389 Final: 25 Phantom: 4",
+        ]);
+        assert_eq!(
+            [
+                event.soft_refs,
+                event.weak_refs,
+                event.final_refs,
+                event.phantom_refs
+            ],
+            [Some(0), Some(389), Some(25), Some(4)]
+        );
+
+        // 並行サイクルの Preclean は Pause の参照処理に含めない
+        let event =
+            apply(&["[3.613s][debug][gc,ref       ] GC(3) Preclean SoftReferences 0.021ms"]);
+        assert_eq!(event.soft_ref_ms, None);
+    }
+
+    #[test]
+    fn reads_string_table_cleanup() {
+        // sample_gc.log の Remark
+        let event = apply(&[
+            "[3.617s][info ][gc,stringtable] GC(3) Cleaned string and symbol table, strings: 15464 processed, 0 removed, symbols: 56772 processed, 154 removed",
+        ]);
+        assert_eq!(event.strings_processed, Some(15464));
+        assert_eq!(event.strings_removed, Some(0));
+        assert_eq!(event.symbols_processed, Some(56772));
+        assert_eq!(event.symbols_removed, Some(154));
+    }
+
+    #[test]
+    fn reads_and_strips_legacy_references() {
+        let header = "[GC (Allocation Failure) 2025-04-11T09:50:19.101+0900: 1.801: [ParNew2025-04-11T09:50:19.105+0900: 1.805: [SoftReference, 0 refs, 0.0000080 secs]2025-04-11T09:50:19.105+0900: 1.805: [WeakReference, 12 refs, 0.0000050 secs]2025-04-11T09:50:19.105+0900: 1.805: [FinalReference, 4817 refs, 0.0301200 secs]2025-04-11T09:50:19.135+0900: 1.835: [PhantomReference, 0 refs, 2 refs, 0.0000060 secs]2025-04-11T09:50:19.135+0900: 1.835: [JNI Weak Reference, 0.0000040 secs]: 157248K->17472K(157248K), 0.0523410 secs]";
+        assert_eq!(
+            strip_legacy_references(header),
+            "[GC (Allocation Failure) 2025-04-11T09:50:19.101+0900: 1.801: [ParNew: 157248K->17472K(157248K), 0.0523410 secs]"
+        );
+
+        let mut event = GCEvent::default();
+        apply_legacy_references(&mut event, header);
+        assert_eq!(
+            [
+                event.soft_refs,
+                event.weak_refs,
+                event.final_refs,
+                event.phantom_refs
+            ],
+            [Some(0), Some(12), Some(4817), Some(0)]
+        );
+        assert_eq!(event.final_ref_ms, Some(30.12));
+        assert!((event.phantom_ref_ms.unwrap() - 0.006).abs() < 1e-9);
+
+        // G1 の JDK 8 の "[Ref Proc: 0.6 ms]" (sample_gc_jdk8.log)
+        let mut event = GCEvent::default();
+        apply_legacy_references(&mut event, "      [Ref Proc: 0.6 ms]");
+        assert_eq!(event.ref_processing_ms, Some(0.6));
+
+        // 参照型の出力の無い行はそのまま
+        let plain = "[ParNew: 139776K->17472K(157248K), 0.0412340 secs]";
+        assert!(matches!(strip_legacy_references(plain), Cow::Borrowed(_)));
+    }
+}
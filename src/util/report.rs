@@ -0,0 +1,49 @@
+use chrono::{DateTime, FixedOffset};
+use std::fmt::Display;
+
+// 一覧に表示する件数の上限 (超えた分は件数だけ表示する)
+pub const REPORT_LIMIT: usize = 20;
+pub const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+pub const TIME_FORMAT: &str = "%H:%M:%S%.3f";
+
+// "GC(12)" (GC ID の無い JDK 8 のイベントは空)
+pub fn fmt_gc_id(gc_id: Option<u64>) -> String {
+    gc_id.map(|id| format!("GC({})", id)).unwrap_or_default()
+}
+
+// 日時のデコレーションが無いログは JVM 起動からの経過秒数で表示する
+pub fn fmt_time_label(
+    time: Option<DateTime<FixedOffset>>,
+    uptime_secs: Option<f64>,
+    format: &str,
+) -> String {
+    match (time, uptime_secs) {
+        (Some(time), _) => time.format(format).to_string(),
+        (None, Some(uptime)) => format!("{:.3}s", uptime),
+        (None, None) => "-".to_string(),
+    }
+}
+
+pub fn print_report_header(title: &str) {
+    println!("{}", title);
+    println!("────────────────────────");
+}
+
+// "Pauses with worker stats             : 12 回" の形の1行
+pub fn print_row(label: impl Display, value: impl Display) {
+    println!("{:<36} : {}", label, value);
+}
+
+pub fn print_count_row(label: impl Display, count: impl Display) {
+    print_row(label, format!("{:>4} 回", count));
+}
+
+// 先頭の REPORT_LIMIT 件を表示し、残りは件数だけ表示する
+pub fn print_limited<T>(items: &[T], line: impl Fn(&T) -> String) {
+    for item in items.iter().take(REPORT_LIMIT) {
+        println!("{}", line(item));
+    }
+    if items.len() > REPORT_LIMIT {
+        println!("... 他 {} 件", items.len() - REPORT_LIMIT);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::model::{GCEvent, GcCause, GcPhase, detect_gc_type};
+use crate::util::parser::parse_size;
+use regex::Regex;
+use std::sync::LazyLock;
+
+// "GC(0) Pause Init Mark (unload classes) 0.234ms" / "GC(0) Concurrent cleanup 260M->60M(4096M) 0.2ms"
+static RE_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"GC\(\d+\) ((?:Pause|Concurrent) [^\d]*?)(?: (\d+)([BKMG])->(\d+)([BKMG])\((\d+)([BKMG])\))? (\d+\.\d+)ms$",
+    )
+    .unwrap()
+});
+// サイクルの開始前に GC(N) を付けずに出力される "[gc,ergo] Trigger: ..."
+static RE_TRIGGER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"Trigger: (.+)$").unwrap());
+
+// "Trigger: Free (965M) is below minimum threshold (409M)" 等をGCの原因に変換する
+pub fn detect_shenandoah_trigger(line: &str) -> Option<GcCause> {
+    let caps = RE_TRIGGER.captures(line)?;
+    let text = &caps[1];
+    Some(if text.contains("below minimum threshold") {
+        GcCause::MinimumFreeThreshold
+    } else if text.contains("below learning threshold") {
+        GcCause::LearningThreshold
+    } else if text.contains("allocation rate") || text.contains("Instantaneous rate") {
+        GcCause::AllocationRate
+    } else if text.contains("Time since last GC") {
+        GcCause::Timer
+    } else if text.contains("Handle Allocation Failure") {
+        GcCause::AllocationFailure
+    } else {
+        GcCause::Other
+    })
+}
+
+// Shenandoah の並行フェーズ行をサイクルのイベントに反映する
+// Pause の行はサイクルとは別のイベントとして返す (Degenerated GC / Full GC はサイクルの終了を兼ねる)
+pub fn apply_shenandoah_line(event: &mut GCEvent, line: &str) -> Option<GCEvent> {
+    let caps = RE_PHASE.captures(line)?;
+    let phase = GcPhase::new(&caps[1], caps[8].parse().unwrap_or(0.0));
+
+    if !phase.is_pause() {
+        // サイクル全体の使用量は最初と最後にヒープ情報が出たフェーズから求める
+        if caps.get(2).is_some() {
+            if event.heap_used_before.is_none() {
+                event.heap_used_before = parse_size(&caps[2], &caps[3]);
+            }
+            event.heap_used_after = parse_size(&caps[4], &caps[5]);
+            event.heap_committed = parse_size(&caps[6], &caps[7]);
+        }
+        event.phases.push(phase);
+        return None;
+    }
+
+    // ヒープ情報が付くのは Degenerated GC / Full GC の行のみ
+    let mut pause = GCEvent {
+        gc_id: event.gc_id,
+        gc_type: detect_gc_type(&phase.name),
+        time: event.time,
+        uptime_secs: event.uptime_secs,
+        has_pause: true,
+        pause_time_ms: Some(phase.duration_ms),
+        ..Default::default()
+    };
+    if caps.get(2).is_some() {
+        pause.heap_used_before = parse_size(&caps[2], &caps[3]);
+        pause.heap_used_after = parse_size(&caps[4], &caps[5]);
+        pause.heap_committed = parse_size(&caps[6], &caps[7]);
+        // STW で続行したサイクルの回収結果はその Pause の結果とする
+        event.heap_used_after = pause.heap_used_after;
+        event.heap_committed = pause.heap_committed;
+    }
+    Some(pause)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::GcType;
+    use crate::util::parser::parse_gc_events;
+    use crate::util::units::MB;
+
+    fn sample_events() -> Vec<GCEvent> {
+        let lines = include_str!("../../sample/sample_shenandoah.log")
+            .lines()
+            .map(String::from);
+        parse_gc_events(lines).unwrap().events
+    }
+
+    fn count(events: &[GCEvent], gc_type: GcType) -> usize {
+        events.iter().filter(|e| e.gc_type == gc_type).count()
+    }
+
+    #[test]
+    fn records_each_pause_as_its_own_event() {
+        let events = sample_events();
+        assert_eq!(events.len(), 48);
+        assert_eq!(count(&events, GcType::Concurrent), 10);
+        assert_eq!(count(&events, GcType::InitMark), 10);
+        assert_eq!(count(&events, GcType::InitialMark), 0);
+        assert_eq!(count(&events, GcType::FinalMark), 9);
+        assert_eq!(count(&events, GcType::InitUpdateRefs), 9);
+        assert_eq!(count(&events, GcType::FinalUpdateRefs), 9);
+        assert_eq!(count(&events, GcType::Degenerated), 1);
+
+        let cycle0: Vec<&GCEvent> = events.iter().filter(|e| e.gc_id == Some(0)).collect();
+        let pauses: Vec<(GcType, f64)> = cycle0
+            .iter()
+            .filter(|e| e.has_pause)
+            .map(|e| (e.gc_type.clone(), e.pause_time_ms.unwrap()))
+            .collect();
+        assert_eq!(
+            pauses,
+            vec![
+                (GcType::InitMark, 0.411),
+                (GcType::FinalMark, 0.921),
+                (GcType::InitUpdateRefs, 0.052),
+                (GcType::FinalUpdateRefs, 0.333),
+            ]
+        );
+
+        // サイクルのイベントは並行フェーズとヒープの推移だけを持つ
+        let cycle = cycle0
+            .iter()
+            .find(|e| e.gc_type == GcType::Concurrent)
+            .unwrap();
+        assert!(!cycle.has_pause);
+        assert_eq!(cycle.pause_time_ms, None);
+        assert!(cycle.phases.iter().all(|p| !p.is_pause()));
+        assert_eq!(cycle.gc_cause, Some(GcCause::MinimumFreeThreshold));
+        assert_eq!(cycle.heap_used_before, Some(600.0 * MB));
+        assert_eq!(cycle.heap_used_after, Some(172.0 * MB));
+        assert_eq!(cycle.heap_committed, Some(4096.0 * MB));
+    }
+
+    #[test]
+    fn degenerated_gc_keeps_only_its_own_pause() {
+        let events = sample_events();
+        let degenerated = events
+            .iter()
+            .find(|e| e.gc_type == GcType::Degenerated)
+            .unwrap();
+        assert_eq!(degenerated.gc_id, Some(6));
+        assert_eq!(degenerated.pause_time_ms, Some(114.474));
+        assert!(degenerated.phases.is_empty());
+        assert_eq!(degenerated.heap_used_before, Some(3186.0 * MB));
+        assert_eq!(degenerated.heap_used_after, Some(939.0 * MB));
+
+        // 中断されたサイクルの回収結果は Degenerated GC の結果になる
+        let cycle = events
+            .iter()
+            .find(|e| e.gc_id == Some(6) && e.gc_type == GcType::Concurrent)
+            .unwrap();
+        assert_eq!(cycle.heap_used_after, Some(939.0 * MB));
+        assert_eq!(
+            cycle
+                .phases
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Concurrent reset"]
+        );
+    }
+
+    #[test]
+    fn parses_trigger_into_cause() {
+        let cases = [
+            (
+                "[info][gc,ergo] Trigger: Free (965M) is below minimum threshold (409M)",
+                GcCause::MinimumFreeThreshold,
+            ),
+            (
+                "[info][gc] Trigger: Learning 1 of 5. Free (2867M) is below learning threshold (2867M)",
+                GcCause::LearningThreshold,
+            ),
+            (
+                "[info][gc] Trigger: Average GC time (12.34 ms) is above the time for average allocation rate (1024 MB/s) to deplete free headspace (100M) (margin of error = 1.80)",
+                GcCause::AllocationRate,
+            ),
+            (
+                "[info][gc] Trigger: Time since last GC (300004 ms) is larger than guaranteed interval (300000 ms)",
+                GcCause::Timer,
+            ),
+            ("[info][gc] Trigger: Something new", GcCause::Other),
+        ];
+        for (line, cause) in cases {
+            assert_eq!(detect_shenandoah_trigger(line), Some(cause), "{}", line);
+        }
+        assert_eq!(
+            detect_shenandoah_trigger("[info][gc] GC(0) Concurrent reset"),
+            None
+        );
+    }
+}
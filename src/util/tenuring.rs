@@ -0,0 +1,76 @@
+use crate::model::{AgeBytes, GCEvent};
+use regex::Regex;
+use std::sync::LazyLock;
+
+// [gc,age] "Desired survivor size 20971520 bytes, new threshold 15 (max threshold 15)"
+// JDK 8 の -XX:+PrintTenuringDistribution は "(max 15)"
+static RE_TENURING_THRESHOLD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"Desired survivor size (\d+) bytes, new threshold (\d+) \(max(?: threshold)? (\d+)\)",
+    )
+    .unwrap()
+});
+// "- age   1:    8386048 bytes,    8386048 total"
+static RE_AGE_ENTRY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"- age +(\d+): +(\d+) bytes, +\d+ total").unwrap());
+
+// テニュアリングのしきい値・年齢ごとのバイト数の行なら true を返す
+pub fn is_tenuring_line(line: &str) -> bool {
+    RE_TENURING_THRESHOLD.is_match(line) || RE_AGE_ENTRY.is_match(line)
+}
+
+// テニュアリングのしきい値と年齢ごとのバイト数をイベントに反映する
+pub fn apply_tenuring_line(event: &mut GCEvent, line: &str) {
+    if let Some(caps) = RE_TENURING_THRESHOLD.captures(line) {
+        event.desired_survivor_size = caps[1].parse().ok();
+        event.tenuring_threshold = caps[2].parse().ok();
+        event.max_tenuring_threshold = caps[3].parse().ok();
+    } else if let Some(caps) = RE_AGE_ENTRY.captures(line)
+        && let (Ok(age), Ok(bytes)) = (caps[1].parse(), caps[2].parse())
+    {
+        event.age_table.push(AgeBytes { age, bytes });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_threshold_and_age_table() {
+        let mut event = GCEvent::default();
+        for line in [
+            // sample_gc.log の GC(7)
+            "[8.067s][debug][gc,age        ] GC(7) Desired survivor size 29360128 bytes, new threshold 5 (max threshold 15)",
+            "[8.067s][trace][gc,age        ] GC(7) Age table with threshold 5 (max threshold 15)",
+            "[8.067s][trace][gc,age        ] GC(7) - age   1:    8386048 bytes,    8386048 total",
+            "[8.067s][trace][gc,age        ] GC(7) - age   2:    2097152 bytes,   10483200 total",
+        ] {
+            apply_tenuring_line(&mut event, line);
+        }
+        assert_eq!(event.desired_survivor_size, Some(29360128.0));
+        assert_eq!(event.tenuring_threshold, Some(5));
+        assert_eq!(event.max_tenuring_threshold, Some(15));
+        let ages: Vec<(u32, f64)> = event.age_table.iter().map(|e| (e.age, e.bytes)).collect();
+        assert_eq!(ages, [(1, 8386048.0), (2, 2097152.0)]);
+    }
+
+    #[test]
+    fn reads_legacy_tenuring_distribution() {
+        // JDK 8 の -XX:+PrintTenuringDistribution は "(max 15)" で、行頭にタイムスタンプが無い
+        let mut event = GCEvent::default();
+        for line in [
+            "Desired survivor size 8945664 bytes, new threshold 1 (max 15)",
+            "- age   1:   17891328 bytes,   17891328 total",
+        ] {
+            apply_tenuring_line(&mut event, line);
+        }
+        assert_eq!(event.desired_survivor_size, Some(8945664.0));
+        assert_eq!(event.tenuring_threshold, Some(1));
+        assert_eq!(event.max_tenuring_threshold, Some(15));
+        assert_eq!(event.age_table.len(), 1);
+        assert!(!is_tenuring_line(
+            "GC(7) Age table with threshold 5 (max threshold 15)"
+        ));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::model::{GCEvent, JvmHeader};
+use anyhow::bail;
+
+// 表示用の換算に使う 1MB / 1GB のバイト数
+pub const MB: f64 = 1024.0 * 1024.0;
+pub const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+// --units で指定するヒープ量の単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapUnit {
+    Bytes,
+    Mb,
+    Gb,
+    Regions,
+}
+
+impl HeapUnit {
+    pub fn parse(value: &str) -> Result<HeapUnit, anyhow::Error> {
+        match value.to_ascii_lowercase().as_str() {
+            "bytes" | "b" => Ok(HeapUnit::Bytes),
+            "mb" | "m" => Ok(HeapUnit::Mb),
+            "gb" | "g" => Ok(HeapUnit::Gb),
+            "regions" => Ok(HeapUnit::Regions),
+            _ => bail!("invalid unit: {} (bytes, MB, GB, regions)", value),
+        }
+    }
+
+    // clap の value_parser 用: parse と同じ値を受け付け、指定された文字列のまま返す
+    pub fn parse_arg(value: &str) -> Result<String, anyhow::Error> {
+        HeapUnit::parse(value).map(|_| value.to_string())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeapUnit::Bytes => "bytes",
+            HeapUnit::Mb => "MB",
+            HeapUnit::Gb => "GB",
+            HeapUnit::Regions => "regions",
+        }
+    }
+
+    // 1単位あたりのバイト数
+    fn bytes_per_unit(&self, region_size: Option<f64>) -> Option<f64> {
+        match self {
+            HeapUnit::Bytes => Some(1.0),
+            HeapUnit::Mb => Some(MB),
+            HeapUnit::Gb => Some(GB),
+            HeapUnit::Regions => region_size,
+        }
+    }
+
+    // ヘッダ等のバイト数を表示用に換算する
+    pub fn format_bytes(&self, bytes: f64, region_size: Option<f64>) -> String {
+        match self.bytes_per_unit(region_size) {
+            Some(scale) if *self == HeapUnit::Bytes => {
+                format!("{:.0} {}", bytes / scale, self.label())
+            }
+            Some(scale) => format!("{:.1} {}", bytes / scale, self.label()),
+            None => format!("{:.1} MB", bytes / MB),
+        }
+    }
+}
+
+// イベントのヒープ量を指定の単位に揃える
+// G1 の Eden / Survivor / Old / Humongous はリージョン数、それ以外はバイト数で記録されている
+// Metaspace はヒープ外なのでリージョン数には換算しない
+pub fn convert_heap_units(
+    events: &mut [GCEvent],
+    unit: HeapUnit,
+    header: &JvmHeader,
+) -> Result<(), anyhow::Error> {
+    let has_regions = events.iter().any(|e| e.eden_before.is_some());
+    let region_size = header.region_size;
+    if region_size.is_none() && (unit == HeapUnit::Regions || has_regions) {
+        bail!(
+            "--units {} needs the heap region size, but the log does not contain it",
+            unit.label()
+        );
+    }
+    let bytes_per_unit = unit.bytes_per_unit(region_size).unwrap_or(1.0);
+    let region_scale = region_size.unwrap_or(1.0) / bytes_per_unit;
+    let byte_scale = 1.0 / bytes_per_unit;
+    let metaspace_scale = if unit == HeapUnit::Regions {
+        1.0
+    } else {
+        byte_scale
+    };
+
+    for event in events {
+        for value in [
+            &mut event.eden_before,
+            &mut event.eden_after,
+            &mut event.eden_total,
+            &mut event.survivor_before,
+            &mut event.survivor_after,
+            &mut event.survivor_total,
+            &mut event.old_before,
+            &mut event.old_after,
+            &mut event.humongous_before,
+            &mut event.humongous_after,
+            &mut event.block_young_regions_before,
+            &mut event.block_young_regions_after,
+            &mut event.block_survivor_regions_before,
+            &mut event.block_survivor_regions_after,
+        ] {
+            scale(value, region_scale);
+        }
+        for value in [
+            &mut event.heap_used_before,
+            &mut event.heap_used_after,
+            &mut event.heap_committed,
+            &mut event.young_gen_before,
+            &mut event.young_gen_after,
+            &mut event.young_gen_total,
+            &mut event.old_gen_before,
+            &mut event.old_gen_after,
+            &mut event.old_gen_total,
+            &mut event.block_heap_used_before,
+            &mut event.block_heap_used_after,
+            &mut event.block_heap_total_before,
+            &mut event.block_heap_total_after,
+            &mut event.desired_survivor_size,
+            &mut event.heap_free,
+            &mut event.heap_live,
+            &mut event.heap_allocated,
+            &mut event.heap_garbage,
+            &mut event.heap_reclaimed,
+        ] {
+            scale(value, byte_scale);
+        }
+        for entry in &mut event.age_table {
+            entry.bytes *= byte_scale;
+        }
+        for value in [
+            &mut event.metaspace_used_before,
+            &mut event.metaspace_used_after,
+            &mut event.metaspace_committed,
+            &mut event.metaspace_reserved,
+            &mut event.class_space_used_before,
+            &mut event.class_space_used_after,
+            &mut event.class_space_committed,
+        ] {
+            scale(value, metaspace_scale);
+        }
+    }
+    Ok(())
+}
+
+fn scale(value: &mut Option<f64>, factor: f64) {
+    if let Some(value) = value {
+        *value *= factor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arg::Args;
+    use crate::cli::generate_cli_app;
+    use crate::model::detect_jvm_header;
+    use crate::util::parse_gc_events;
+    use clap::Parser;
+
+    const SAMPLE: &str = include_str!("../../sample/sample_gc.log");
+
+    // sample_gc.log の GC(0): "Eden regions: 76->0(72)", "Pause Young ... 307M->17M(6144M)"
+    fn first_gc() -> Vec<GCEvent> {
+        let log = parse_gc_events(SAMPLE.lines().take(61).map(String::from)).unwrap();
+        log.events.into_iter().take(1).collect()
+    }
+
+    fn region_header() -> JvmHeader {
+        detect_jvm_header(&[
+            "[2025-04-11T09:50:17.321+0900][0.015s][info][gc,heap] Heap region size: 4M"
+                .to_string(),
+        ])
+    }
+
+    #[test]
+    fn converts_regions_to_bytes() {
+        let mut events = first_gc();
+        assert_eq!(events[0].eden_before, Some(76.0));
+        convert_heap_units(&mut events, HeapUnit::Bytes, &region_header()).unwrap();
+        assert_eq!(events[0].eden_before, Some(76.0 * 4.0 * MB));
+        assert_eq!(events[0].survivor_after, Some(4.0 * 4.0 * MB));
+        assert_eq!(events[0].heap_used_before, Some(307.0 * MB));
+        assert_eq!(events[0].metaspace_used_before, Some(15646.0 * 1024.0));
+    }
+
+    #[test]
+    fn converts_to_mb_gb_and_regions() {
+        let mut events = first_gc();
+        convert_heap_units(&mut events, HeapUnit::Mb, &region_header()).unwrap();
+        assert_eq!(events[0].eden_before, Some(304.0));
+        assert_eq!(events[0].heap_used_before, Some(307.0));
+        assert_eq!(events[0].heap_committed, Some(6144.0));
+
+        let mut events = first_gc();
+        convert_heap_units(&mut events, HeapUnit::Gb, &region_header()).unwrap();
+        assert_eq!(events[0].heap_committed, Some(6.0));
+
+        // リージョン数ではバイト数のヒープ量をリージョンサイズで割り、Metaspace はそのまま
+        let mut events = first_gc();
+        convert_heap_units(&mut events, HeapUnit::Regions, &region_header()).unwrap();
+        assert_eq!(events[0].eden_before, Some(76.0));
+        assert_eq!(events[0].heap_used_before, Some(76.75));
+        assert_eq!(events[0].metaspace_used_before, Some(15646.0 * 1024.0));
+    }
+
+    #[test]
+    fn needs_region_size_for_region_counts() {
+        let mut events = first_gc();
+        let err = convert_heap_units(&mut events, HeapUnit::Mb, &JvmHeader::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--units MB needs the heap region size, but the log does not contain it"
+        );
+        assert_eq!(events[0].eden_before, Some(76.0));
+
+        // リージョン数を持たないコレクタはリージョンサイズが無くても換算できる
+        let mut events = vec![GCEvent {
+            heap_used_before: Some(512.0 * MB),
+            ..Default::default()
+        }];
+        convert_heap_units(&mut events, HeapUnit::Gb, &JvmHeader::default()).unwrap();
+        assert_eq!(events[0].heap_used_before, Some(0.5));
+        assert!(convert_heap_units(&mut events, HeapUnit::Regions, &JvmHeader::default()).is_err());
+    }
+
+    #[test]
+    fn parses_unit_names() {
+        assert_eq!(HeapUnit::parse("MB").unwrap(), HeapUnit::Mb);
+        assert_eq!(HeapUnit::parse("g").unwrap(), HeapUnit::Gb);
+        assert_eq!(HeapUnit::parse("regions").unwrap(), HeapUnit::Regions);
+        assert_eq!(
+            HeapUnit::parse("KB").unwrap_err().to_string(),
+            "invalid unit: KB (bytes, MB, GB, regions)"
+        );
+    }
+
+    // derive の Args とビルダーの generate_cli_app で、受け付ける値を揃える
+    #[test]
+    fn accepts_same_values_in_both_cli_definitions() {
+        let accepts = |option: &str, value: &str| {
+            let argv = ["gca", "-i", "gc.log", option, value];
+            let derived = Args::try_parse_from(argv).is_ok();
+            let built = generate_cli_app().try_get_matches_from(argv).is_ok();
+            assert_eq!(derived, built, "{} {}", option, value);
+            derived
+        };
+
+        // --units は HeapUnit::parse と同じ値を受け付ける
+        for value in ["bytes", "b", "MB", "mb", "m", "GB", "g", "regions"] {
+            assert!(accepts("--units", value), "{}", value);
+        }
+        let args = Args::try_parse_from(["gca", "-i", "gc.log", "--units", "mb"]).unwrap();
+        assert_eq!(args.units.as_deref(), Some("mb"));
+        assert!(!accepts("--units", "KB"));
+
+        for value in [
+            "imbalance",
+            "cpu",
+            "consistency",
+            "humongous",
+            "tenuring",
+            "references",
+        ] {
+            assert!(accepts("--analyze", value), "{}", value);
+        }
+        assert!(!accepts("--analyze", "memory"));
+
+        for value in ["heap", "pause", "combined", "metaspace"] {
+            assert!(accepts("--mode", value), "{}", value);
+        }
+        assert!(!accepts("--mode", "cpu"));
+    }
+}
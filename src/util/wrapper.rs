@@ -0,0 +1,179 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+// コンテナランタイムやsyslogが付与するラッパーのパターン
+// CRI (containerd / CRI-O): "2025-04-11T00:50:19.433123456Z stdout F [2025-04-11T09:50:19.433+0900][info][gc] ..."
+static RE_CRI: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d{4}-\d\d-\d\dT\S+ (stdout|stderr) ([FP]) (.*)$").unwrap());
+// RFC 5424: "<14>1 2025-04-11T00:50:19.433Z host app 1234 - - [2025-...][info][gc] ..."
+static RE_SYSLOG_5424: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^<\d{1,3}>1 \S+ \S+ \S+ \S+ \S+ (?:-|(?:\[[^\]]*\])+) (.*)$").unwrap()
+});
+// RFC 3164: "Apr 11 09:50:19 host java[1234]: [2025-...][info][gc] ..."
+static RE_SYSLOG_3164: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:<\d{1,3}>)?[A-Z][a-z]{2} [ \d]\d \d\d:\d\d:\d\d \S+ [^\s:\[]+(?:\[\d+\])?: (.*)$",
+    )
+    .unwrap()
+});
+// kubectl logs --timestamps / docker logs -t: "2025-04-11T00:50:19.433123456Z [2025-...][info][gc] ..."
+// JDK 8 形式の "2025-04-11T09:50:19.433+0900: " とはオフセットの書式で区別する
+static RE_RFC3339_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d(?:\.\d+)?(?:Z|[+-]\d\d:\d\d) (.*)$").unwrap()
+});
+
+// Docker json-file ドライバの1行
+#[derive(Deserialize)]
+struct DockerJsonLine {
+    log: String,
+    #[serde(default)]
+    stream: String,
+}
+
+// 行ストリームからラッパーを剥がし、GCログ本体の行だけを返す
+pub fn unwrap_log_lines<I>(lines: I) -> impl Iterator<Item = String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut unwrapper = LineUnwrapper::default();
+    lines
+        .into_iter()
+        .filter_map(move |line| unwrapper.unwrap(&line))
+}
+
+// 分割された行 (CRI の "P"、改行で終わらない Docker の log) はストリームごとに連結する
+#[derive(Debug, Default)]
+pub struct LineUnwrapper {
+    partials: HashMap<String, String>,
+}
+
+impl LineUnwrapper {
+    // 連結待ちの行は None を返す
+    pub fn unwrap(&mut self, line: &str) -> Option<String> {
+        if line.starts_with('{')
+            && let Ok(entry) = serde_json::from_str::<DockerJsonLine>(line)
+        {
+            let buffer = self.partials.entry(entry.stream).or_default();
+            match entry.log.strip_suffix('\n') {
+                Some(log) => {
+                    buffer.push_str(log.strip_suffix('\r').unwrap_or(log));
+                    return Some(std::mem::take(buffer));
+                }
+                None => {
+                    buffer.push_str(&entry.log);
+                    return None;
+                }
+            }
+        }
+
+        if let Some(caps) = RE_CRI.captures(line) {
+            let buffer = self.partials.entry(caps[1].to_string()).or_default();
+            buffer.push_str(&caps[3]);
+            return if &caps[2] == "F" {
+                Some(std::mem::take(buffer))
+            } else {
+                None
+            };
+        }
+
+        for re in [&RE_SYSLOG_5424, &RE_SYSLOG_3164, &RE_RFC3339_PREFIX] {
+            if let Some(caps) = re.captures(line) {
+                return Some(caps[1].to_string());
+            }
+        }
+
+        Some(line.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parser::parse_gc_events;
+
+    const GC_LINE: &str = "[2025-04-11T09:50:19.433+0900][2.127s][info][gc] GC(0) Pause Young (Normal) (G1 Evacuation Pause) 307M->17M(6144M) 21.425ms";
+
+    fn unwrap_all(lines: &[&str]) -> Vec<String> {
+        unwrap_log_lines(lines.iter().map(|line| line.to_string())).collect()
+    }
+
+    #[test]
+    fn unwraps_cri_lines() {
+        let full = format!("2025-04-11T00:50:19.433123456Z stdout F {}", GC_LINE);
+        assert_eq!(unwrap_all(&[&full]), vec![GC_LINE]);
+
+        // "P" の行は次の "F" の行まで連結する
+        let (head, tail) = GC_LINE.split_at(40);
+        let partial = format!("2025-04-11T00:50:19.433123456Z stdout P {}", head);
+        let rest = format!("2025-04-11T00:50:19.433223456Z stdout F {}", tail);
+        assert_eq!(unwrap_all(&[&partial, &rest]), vec![GC_LINE]);
+    }
+
+    #[test]
+    fn unwraps_docker_json_lines() {
+        let line = serde_json::json!({
+            "log": format!("{}\n", GC_LINE),
+            "stream": "stdout",
+            "time": "2025-04-11T00:50:19.433123456Z",
+        })
+        .to_string();
+        assert_eq!(unwrap_all(&[&line]), vec![GC_LINE]);
+
+        // 16KB を超える行は改行で終わらない log に分割される
+        let (head, tail) = GC_LINE.split_at(40);
+        let first = serde_json::json!({ "log": head, "stream": "stdout" }).to_string();
+        let second =
+            serde_json::json!({ "log": format!("{}\r\n", tail), "stream": "stdout" }).to_string();
+        assert_eq!(unwrap_all(&[&first, &second]), vec![GC_LINE]);
+    }
+
+    #[test]
+    fn unwraps_syslog_lines() {
+        let rfc5424 = format!(
+            "<14>1 2025-04-11T00:50:19.433Z host app 1234 - - {}",
+            GC_LINE
+        );
+        let rfc5424_sd = format!(
+            "<14>1 2025-04-11T00:50:19.433Z host app 1234 ID47 [exampleSDID@32473 iut=\"3\"] {}",
+            GC_LINE
+        );
+        let rfc3164 = format!("Apr 11 09:50:19 host java[1234]: {}", GC_LINE);
+        let rfc3164_pri = format!("<14>Apr  1 09:50:19 host java: {}", GC_LINE);
+        let kubectl = format!("2025-04-11T00:50:19.433123456Z {}", GC_LINE);
+        for line in [rfc5424, rfc5424_sd, rfc3164, rfc3164_pri, kubectl] {
+            assert_eq!(unwrap_all(&[&line]), vec![GC_LINE], "{}", line);
+        }
+    }
+
+    #[test]
+    fn keeps_unwrapped_lines() {
+        let jdk8 = "2025-04-11T09:50:19.433+0900: 2.127: [GC pause (G1 Evacuation Pause) (young), 0.0214250 secs]";
+        assert_eq!(unwrap_all(&[GC_LINE, jdk8]), vec![GC_LINE, jdk8]);
+    }
+
+    #[test]
+    fn parses_wrapped_sample_like_the_original() {
+        let sample = include_str!("../../sample/sample_gc.log");
+        let wrapped: Vec<String> = sample
+            .lines()
+            .map(|line| format!("2025-04-11T00:50:19.433123456Z stdout F {}", line))
+            .collect();
+        let expected = parse_gc_events(sample.lines().map(String::from)).unwrap();
+        let actual = parse_gc_events(unwrap_log_lines(wrapped)).unwrap();
+        assert_eq!(actual.events.len(), expected.events.len());
+        assert_eq!(
+            actual
+                .events
+                .iter()
+                .map(|e| e.pause_time_ms)
+                .collect::<Vec<_>>(),
+            expected
+                .events
+                .iter()
+                .map(|e| e.pause_time_ms)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::model::{GCEvent, GcPhase};
+use crate::util::parser::parse_size;
+use regex::Regex;
+use std::sync::LazyLock;
+
+// "GC(0) Pause Mark Start 0.010ms" / "GC(0) Y: Concurrent Mark 3.051ms"
+static RE_PHASE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"GC\(\d+\) ((?:[yYO]: )?(?:Pause|Concurrent) [^\d]*?) (\d+\.\d+)ms$").unwrap()
+});
+// "GC(0)      Used:       56M (1%)   60M (1%) ..." / "GC(0) Y: Live: ..."
+static RE_TABLE_ROW: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"GC\(\d+\) (?:([yYO]): )?\s*(Capacity|Free|Used|Live|Allocated|Garbage|Reclaimed):\s+(.*)$")
+        .unwrap()
+});
+static RE_TABLE_CELL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"-|(\d+)([KMG])\s*\(\s*\d+%\)").unwrap());
+// "GC(0) Garbage Collection (Warmup) 56M(1%)->18M(0%)" / "GC(1) Minor Collection (Allocation Rate) 150M(4%)->40M(1%) 0.012s"
+static RE_SUMMARY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:Garbage|Minor|Major) Collection \(.*?\) (\d+)([KMG])\(\d+%\)->(\d+)([KMG])\(\d+%\)",
+    )
+    .unwrap()
+});
+
+// ヒープテーブルは Mark Start / Mark End / Relocate Start / Relocate End / High / Low の順
+const RELOCATE_END: usize = 3;
+
+// ZGC のフェーズ行・ヒープテーブル・サマリ行をイベントに反映する
+// サマリ行を読んだ場合はサイクル終了として true を返す
+pub fn apply_zgc_line(event: &mut GCEvent, line: &str) -> bool {
+    if let Some(caps) = RE_PHASE.captures(line) {
+        let phase = GcPhase::new(&caps[1], caps[2].parse().unwrap_or(0.0));
+        if phase.is_pause() {
+            event.has_pause = true;
+            event.pause_time_ms = Some(event.pause_time_ms.unwrap_or(0.0) + phase.duration_ms);
+        }
+        event.phases.push(phase);
+        return false;
+    }
+
+    if let Some(caps) = RE_TABLE_ROW.captures(line) {
+        let cells: Vec<Option<f64>> = RE_TABLE_CELL
+            .captures_iter(&caps[3])
+            .map(|cell| match (cell.get(1), cell.get(2)) {
+                (Some(value), Some(unit)) => parse_size(value.as_str(), unit.as_str()),
+                _ => None,
+            })
+            .collect();
+        let Some(value) = cells.get(RELOCATE_END).copied().flatten() else {
+            return false;
+        };
+        // 世代別ZGCでは Y:/O: のテーブルが世代ごとに出るので合算する
+        let generational = caps.get(1).is_some();
+        let merge = |field: &mut Option<f64>| {
+            *field = Some(if generational {
+                field.unwrap_or(0.0) + value
+            } else {
+                value
+            });
+        };
+        match &caps[2] {
+            "Capacity" => event.heap_committed = Some(value),
+            "Free" => event.heap_free = Some(value),
+            "Live" => merge(&mut event.heap_live),
+            "Allocated" => merge(&mut event.heap_allocated),
+            "Garbage" => merge(&mut event.heap_garbage),
+            "Reclaimed" => merge(&mut event.heap_reclaimed),
+            _ => {}
+        }
+        return false;
+    }
+
+    if let Some(caps) = RE_SUMMARY.captures(line) {
+        event.heap_used_before = parse_size(&caps[1], &caps[2]);
+        event.heap_used_after = parse_size(&caps[3], &caps[4]);
+        return true;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{GCEvent, GcCause, GcType};
+    use crate::util::parser::parse_gc_events;
+    use crate::util::units::MB;
+
+    fn parse(text: &str) -> Vec<GCEvent> {
+        parse_gc_events(text.lines().map(String::from))
+            .unwrap()
+            .events
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn parses_zgc_sample_cycles() {
+        let events = parse(include_str!("../../sample/sample_zgc.log"));
+
+        assert_eq!(events.len(), 6);
+        assert!(events.iter().all(|e| e.gc_type == GcType::Concurrent));
+
+        let first = &events[0];
+        assert_eq!(first.gc_id, Some(0));
+        assert_eq!(first.gc_cause, Some(GcCause::Warmup));
+        // Pause Mark Start + Pause Mark End + Pause Relocate Start
+        assert!((first.pause_time_ms.unwrap() - 0.111).abs() < 1e-9);
+        assert_eq!(first.heap_used_before, Some(56.0 * MB));
+        assert_eq!(first.heap_used_after, Some(18.0 * MB));
+        assert_eq!(first.heap_committed, Some(4096.0 * MB));
+        assert_eq!(first.phases.iter().filter(|p| p.is_pause()).count(), 3);
+        assert_eq!(events[2].gc_cause, Some(GcCause::AllocationRate));
+    }
+
+    #[test]
+    fn tells_minor_from_major_collections() {
+        let events = parse(include_str!("../../sample/sample_zgc_generational.log"));
+        let kinds: Vec<(Option<u64>, GcType)> = events
+            .iter()
+            .map(|e| (e.gc_id, e.gc_type.clone()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (Some(0), GcType::Major),
+                (Some(1), GcType::Minor),
+                (Some(2), GcType::Minor),
+                (Some(3), GcType::Major),
+                (Some(4), GcType::Minor),
+                (Some(5), GcType::Minor),
+            ]
+        );
+        assert_eq!(events[0].gc_cause, Some(GcCause::Warmup));
+        assert_eq!(events[5].gc_cause, Some(GcCause::HighUsage));
+
+        // Minor は y: の Pause だけ、Major は Y: と O: の Pause を合計する
+        let minor = &events[1];
+        assert_close(minor.pause_time_ms, 0.011 + 0.018 + 0.010);
+        assert_close(minor.heap_used_before, 900.0 * MB);
+        assert_close(minor.heap_used_after, 108.0 * MB);
+        let major = &events[0];
+        assert_close(major.pause_time_ms, 0.012 + 0.021 + 0.009 + 0.015 + 0.008);
+        assert_eq!(major.phases.iter().filter(|p| p.is_pause()).count(), 5);
+    }
+
+    #[test]
+    fn merges_generation_tables() {
+        let events = parse(include_str!("../../sample/sample_zgc_generational.log"));
+        let find = |id: u64| events.iter().find(|e| e.gc_id == Some(id)).unwrap();
+
+        // Minor は Young Generation Statistics の Relocate End の値
+        let minor = find(1);
+        assert_close(minor.heap_live, 64.0 * MB);
+        assert_close(minor.heap_garbage, 28.0 * MB);
+        assert_close(minor.heap_allocated, 16.0 * MB);
+        assert_close(minor.heap_reclaimed, 808.0 * MB);
+        assert_close(minor.heap_free, 3988.0 * MB);
+        assert_close(minor.heap_committed, 4096.0 * MB);
+
+        // Major は Y: と O: の Generation Statistics の合計、Free は後に出る O: の Heap Statistics
+        let major = find(3);
+        assert_close(major.heap_live, (50.0 + 48.0) * MB);
+        assert_close(major.heap_garbage, (14.0 + 2.0) * MB);
+        assert_close(major.heap_allocated, (14.0 + 4.0) * MB);
+        assert_close(major.heap_reclaimed, (636.0 + 30.0) * MB);
+        assert_close(major.heap_free, (4096.0 - 172.0) * MB);
+        assert_close(major.heap_used_before, 760.0 * MB);
+        assert_close(major.heap_used_after, 172.0 * MB);
+
+        // Old の並行マーク中に割り込んだ Minor は Major に混ざらない
+        let interleaved = find(4);
+        assert_eq!(interleaved.gc_type, GcType::Minor);
+        assert_close(interleaved.heap_live, 70.0 * MB);
+        assert!(interleaved.phases.iter().all(|p| p.name.starts_with("y: ")));
+        assert!(
+            major
+                .phases
+                .iter()
+                .all(|p| p.name.starts_with("Y: ") || p.name.starts_with("O: "))
+        );
+    }
+}